@@ -0,0 +1,47 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+const N: usize = 1_000_000;
+
+fn mutex_channel() -> Duration {
+    let (tx, rx) = channel::channel();
+    let start = Instant::now();
+    let handle = thread::spawn(move || {
+        for i in 0..N {
+            tx.send(i).unwrap();
+        }
+    });
+    for _ in 0..N {
+        rx.recv().unwrap();
+    }
+    handle.join().unwrap();
+    start.elapsed()
+}
+
+fn spsc_channel() -> Duration {
+    let (tx, rx) = channel::spsc::channel(1024);
+    let start = Instant::now();
+    let handle = thread::spawn(move || {
+        for i in 0..N {
+            tx.send(i).unwrap();
+        }
+    });
+    for _ in 0..N {
+        rx.recv().unwrap();
+    }
+    handle.join().unwrap();
+    start.elapsed()
+}
+
+fn main() {
+    for (name, run) in [
+        ("mutex", mutex_channel as fn() -> Duration),
+        ("spsc", spsc_channel),
+    ] {
+        let elapsed = run();
+        println!(
+            "{name:>6}: {N} items in {elapsed:?} ({:.1} Mitems/s)",
+            N as f64 / elapsed.as_secs_f64() / 1e6
+        );
+    }
+}
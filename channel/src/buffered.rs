@@ -0,0 +1,118 @@
+use crate::{SendError, Sender};
+
+/// Collects items locally and sends them in batches with `Sender::send_all`, taking the
+/// channel lock once per batch instead of once per item.
+///
+/// Like `std::io::BufWriter`, dropping it flushes whatever is pending but ignores a send
+/// error, so a receiver that went away first doesn't crash the sending thread on shutdown.
+/// Call `flush` before dropping to observe the error and get the unsent items back.
+pub struct BufferedSender<T> {
+    sender: Sender<T>,
+    buffer: Vec<T>,
+    capacity: usize,
+}
+
+impl<T> BufferedSender<T> {
+    pub fn new(sender: Sender<T>, capacity: usize) -> Self {
+        assert!(capacity > 0, "buffer capacity must be non-zero");
+        Self {
+            sender,
+            buffer: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Buffers `t`, flushing once the buffer is full. On error every item that didn't make
+    /// it into the channel is handed back.
+    pub fn send(&mut self, t: T) -> Result<(), SendError<Vec<T>>> {
+        self.buffer.push(t);
+        if self.buffer.len() >= self.capacity {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), SendError<Vec<T>>> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.sender.send_all(self.buffer.drain(..))
+    }
+
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<T> Drop for BufferedSender<T> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TryRecvError, channel};
+
+    #[test]
+    fn flush_on_full() {
+        let (tx, rx) = channel();
+        let mut tx = BufferedSender::new(tx, 3);
+
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+
+        tx.send(3).unwrap();
+        assert_eq!(tx.pending(), 0);
+        assert_eq!(rx.try_recv().unwrap(), 1);
+        assert_eq!(rx.try_recv().unwrap(), 2);
+        assert_eq!(rx.try_recv().unwrap(), 3);
+    }
+
+    #[test]
+    fn flush_on_drop() {
+        let (tx, rx) = channel();
+        let mut tx = BufferedSender::new(tx, 10);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(tx);
+
+        assert_eq!(rx.recv().unwrap(), 1);
+        assert_eq!(rx.recv().unwrap(), 2);
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn flush_error() {
+        let (tx, rx) = channel();
+        let mut tx = BufferedSender::new(tx, 10);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(rx);
+
+        assert_eq!(tx.flush().unwrap_err().0, vec![1, 2]);
+        assert_eq!(tx.pending(), 0);
+    }
+
+    #[test]
+    fn drop_ignores_disconnect() {
+        let (tx, rx) = channel();
+        let mut tx = BufferedSender::new(tx, 10);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(rx);
+        drop(tx); // pending items are discarded, not a panic
+    }
+
+    #[test]
+    fn drop_after_failed_flush_is_quiet() {
+        let (tx, rx) = channel();
+        let mut tx = BufferedSender::new(tx, 10);
+        tx.send(1).unwrap();
+        drop(rx);
+        assert!(tx.flush().is_err());
+        drop(tx); // nothing pending, so nothing to report
+    }
+}
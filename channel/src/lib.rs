@@ -1,7 +1,18 @@
 use std::cell::{Cell, UnsafeCell};
 use std::collections::VecDeque;
 use std::marker::PhantomData;
-use std::sync::{Arc, Condvar, Mutex};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::task::Waker;
+use std::time::{Duration, Instant};
+
+pub mod buffered;
+pub mod pool;
+pub mod select;
+pub mod spsc;
+#[cfg(feature = "stream")]
+pub mod stream;
+pub mod throttle;
+pub mod timeout;
 
 // Flavors:
 //  - Synchronous channels: Channel where send() can block. Limited capacity.
@@ -16,17 +27,92 @@ use std::sync::{Arc, Condvar, Mutex};
 //  - Oneshot channels: Any capacity. In practice, only one call to send().
 
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-    let shared = Arc::new(Shared::<T>::new());
-    (
-        Sender {
-            shared: Arc::clone(&shared),
-        },
-        Receiver {
-            shared: Arc::clone(&shared),
-            buffer: UnsafeCell::new(VecDeque::default()),
+    ChannelBuilder::new().build()
+}
+
+/// Unbounded channel that wakes receivers according to `notify`.
+pub fn channel_with_notify<T>(notify: NotifyStrategy) -> (Sender<T>, Receiver<T>) {
+    ChannelBuilder::new().notify(notify).build()
+}
+
+/// Bounded channel holding at most `cap` items; `send` blocks while it is full.
+pub fn sync_channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    ChannelBuilder::new().capacity(cap).build()
+}
+
+/// Bounded channel holding at most `cap` items; `policy` decides what `send` does when full.
+pub fn sync_channel_with_policy<T>(cap: usize, policy: OverflowPolicy) -> (Sender<T>, Receiver<T>) {
+    ChannelBuilder::new().capacity(cap).overflow(policy).build()
+}
+
+/// Configures every channel option in one place; `channel` and friends are shortcuts for it.
+pub struct ChannelBuilder<T> {
+    capacity: Option<usize>,
+    overflow: OverflowPolicy,
+    notify: NotifyStrategy,
+    buffered: Option<bool>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> ChannelBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            capacity: None,
+            overflow: OverflowPolicy::default(),
+            notify: NotifyStrategy::default(),
+            buffered: None,
             phantom: PhantomData,
-        },
-    )
+        }
+    }
+
+    /// Bounds the channel to `cap` queued items. Unbounded if never called.
+    pub fn capacity(mut self, cap: usize) -> Self {
+        self.capacity = Some(cap);
+        self
+    }
+
+    /// What `send` does when a bounded channel is full.
+    pub fn overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    pub fn notify(mut self, strategy: NotifyStrategy) -> Self {
+        self.notify = strategy;
+        self
+    }
+
+    /// Whether a receiver moves the whole shared queue into its private buffer on each lock,
+    /// so following receives don't touch the lock. Good for a single busy consumer, but one
+    /// receiver can then hoard items from its clones, and a bounded channel may hold up to
+    /// twice its capacity in flight. Defaults to true for unbounded channels only.
+    pub fn buffered(mut self, buffered: bool) -> Self {
+        self.buffered = Some(buffered);
+        self
+    }
+
+    pub fn build(self) -> (Sender<T>, Receiver<T>) {
+        if let Some(cap) = self.capacity {
+            assert!(cap > 0, "channel capacity must be non-zero");
+        }
+        let shared = Arc::new(Shared::new(Inner::new(self)));
+        (
+            Sender {
+                shared: Arc::clone(&shared),
+            },
+            Receiver {
+                shared: Arc::clone(&shared),
+                buffer: UnsafeCell::new(VecDeque::default()),
+                phantom: PhantomData,
+            },
+        )
+    }
+}
+
+impl<T> Default for ChannelBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 pub struct Sender<T> {
@@ -50,9 +136,10 @@ impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
         let mut inner = self.shared.inner.lock().unwrap();
         inner.senders -= 1;
-        inner.disconnected = inner.senders == 0;
-        if inner.disconnected {
+        if inner.senders == 0 {
+            inner.disconnected = true;
             self.shared.condvar.notify_all();
+            inner.wake_tasks();
         }
     }
 }
@@ -65,16 +152,58 @@ pub struct Receiver<T> {
 
 unsafe impl<T: Send> Send for Receiver<T> {}
 
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        let mut inner = self.shared.inner.lock().unwrap();
+        inner.receivers += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+            buffer: UnsafeCell::new(VecDeque::default()),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut inner = self.shared.inner.lock().unwrap();
+        inner.receivers -= 1;
+        if inner.receivers == 0 {
+            // nobody is left to read these; drop them outside the lock
+            let queue = std::mem::take(&mut inner.queue);
+            drop(inner);
+            self.shared.not_full.notify_all();
+            self.shared.empty.notify_all();
+            drop(queue);
+        } else if !self.get_buffer().is_empty() {
+            // hand our private backlog back to the remaining receivers, keeping FIFO order.
+            // These items already passed the capacity check when they were sent, so on a
+            // bounded channel the queue may now exceed capacity; senders just stay blocked
+            // until the receivers have worked it back down.
+            let buffer = std::mem::take(self.get_buffer());
+            for t in buffer.into_iter().rev() {
+                inner.queue.push_front(t);
+            }
+            self.shared.condvar.notify_all();
+            inner.wake_tasks();
+        }
+    }
+}
+
 struct Shared<T> {
     inner: Mutex<Inner<T>>,
-    condvar: Condvar,
+    condvar: Condvar,  // receivers wait here for items
+    not_full: Condvar, // senders of a bounded channel wait here for space
+    empty: Condvar,    // senders in block_until_empty wait here for the queue to drain
 }
 
 impl<T> Shared<T> {
-    fn new() -> Self {
+    fn new(inner: Inner<T>) -> Self {
         Self {
-            inner: Mutex::new(Inner::new()),
+            inner: Mutex::new(inner),
             condvar: Condvar::new(),
+            not_full: Condvar::new(),
+            empty: Condvar::new(),
         }
     }
 }
@@ -82,51 +211,278 @@ impl<T> Shared<T> {
 struct Inner<T> {
     queue: VecDeque<T>,
     senders: usize,
-    disconnected: bool,
+    receivers: usize,
+    disconnected: bool, // no more items will arrive: all senders are gone or close_after_flush
+    accepting: bool, // cleared by close_after_flush; unlike disconnected, only senders look at it
+    capacity: Option<usize>, // None = unbounded
+    policy: OverflowPolicy,
+    notify: NotifyStrategy,
+    buffered: bool,     // receivers swap the whole queue into their private buffer
+    wakers: Vec<Waker>, // async receivers waiting for an item or a disconnect
 }
 
 impl<T> Inner<T> {
-    fn new() -> Self {
+    fn new(config: ChannelBuilder<T>) -> Self {
         Self {
             queue: VecDeque::default(),
             senders: 1,
+            receivers: 1,
             disconnected: false,
+            accepting: true,
+            capacity: config.capacity,
+            policy: config.overflow,
+            notify: config.notify,
+            buffered: config.buffered.unwrap_or(config.capacity.is_none()),
+            wakers: Vec::new(),
         }
     }
+
+    // sends fail once nobody can receive or the channel has been closed for sending
+    fn rejects_sends(&self) -> bool {
+        self.receivers == 0 || !self.accepting
+    }
+
+    fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|cap| self.queue.len() >= cap)
+    }
+
+    fn wake_tasks(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// What `send` does when a bounded channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Wait until the receiver makes room.
+    #[default]
+    Block,
+    /// Discard the incoming item and hand it back as `Err(SendError(t))`.
+    DropNewest,
+    /// Evict the oldest queued item to make room ("latest wins").
+    DropOldest,
+}
+
+/// How `send` wakes receivers blocked on an empty channel.
+///
+/// Every send wakes someone, so neither strategy can lose a wakeup. `One` wakes a single
+/// receiver per item, which is all a lone consumer needs. `All` wakes every waiting receiver
+/// so they race for the item; that costs spurious wakeups, but idle receivers of a
+/// multi-consumer channel start pulling work as soon as anything arrives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotifyStrategy {
+    #[default]
+    One,
+    All,
 }
 
 #[derive(Debug)]
 pub struct SendError<T>(pub T);
 
+/// Returned by `Sender::try_send`; both variants hand the item back so it can be retried.
+#[derive(Debug)]
+pub enum TrySendError<T> {
+    /// The bounded channel has no room right now.
+    Full(T),
+    /// Every receiver is gone.
+    Disconnected(T),
+}
+
 #[derive(Debug)]
 pub enum TryRecvError {
     Empty,
     Disconnected,
 }
 
+impl TryRecvError {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, TryRecvError::Empty)
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, TryRecvError::Disconnected)
+    }
+}
+
 #[derive(Debug)]
 pub struct RecvError;
 
+#[derive(Debug)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Disconnected,
+}
+
+impl RecvTimeoutError {
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, RecvTimeoutError::Timeout)
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, RecvTimeoutError::Disconnected)
+    }
+}
+
+/// Non-blocking view of a receiver's state, see `Receiver::recv_status`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RecvStatus<T> {
+    /// An item was available.
+    Message(T),
+    /// Nothing is queued right now, but senders are still connected.
+    WouldBlock,
+    /// All senders are gone and every queued item has been received.
+    Closed,
+}
+
 impl<T> Sender<T> {
     pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        let inner = self.shared.inner.lock().unwrap();
+        self.push(inner, t).1
+    }
+
+    /// Like `send`, but never blocks: a full channel returns the item instead of waiting,
+    /// whatever its `OverflowPolicy`.
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        let inner = self.shared.inner.lock().unwrap();
+        if inner.rejects_sends() {
+            return Err(TrySendError::Disconnected(t));
+        }
+        if inner.is_full() {
+            return Err(TrySendError::Full(t));
+        }
+        self.push(inner, t)
+            .1
+            .map_err(|SendError(t)| TrySendError::Disconnected(t))
+    }
+
+    /// Sends every item under a single lock acquisition. Stops at the first item that can't
+    /// be sent and hands it back together with the rest.
+    pub fn send_all<I: IntoIterator<Item = T>>(&self, items: I) -> Result<(), SendError<Vec<T>>> {
+        let mut items = items.into_iter();
         let mut inner = self.shared.inner.lock().unwrap();
-        if inner.disconnected {
-            return Err(SendError(t));
+        for t in items.by_ref() {
+            let (guard, res) = self.push(inner, t);
+            inner = guard;
+            if let Err(SendError(t)) = res {
+                drop(inner);
+                return Err(SendError(std::iter::once(t).chain(items).collect()));
+            }
         }
-        inner.queue.push_back(t);
-        if inner.queue.len() == 1 {
-            self.shared.condvar.notify_one();
+        Ok(())
+    }
+
+    /// Non-blocking `send_all`: sends items under a single lock until the channel is full
+    /// or disconnected, then hands back the unsent rest along with why it stopped.
+    pub fn try_send_all<I: IntoIterator<Item = T>>(
+        &self,
+        items: I,
+    ) -> Result<(), (Vec<T>, TrySendError<()>)> {
+        let mut items = items.into_iter();
+        let mut inner = self.shared.inner.lock().unwrap();
+        while let Some(t) = items.next() {
+            let err = if inner.rejects_sends() {
+                TrySendError::Disconnected(())
+            } else if inner.is_full() {
+                TrySendError::Full(())
+            } else {
+                // not full, so push can't block or drop anything
+                inner = self.push(inner, t).0;
+                continue;
+            };
+            drop(inner);
+            return Err((std::iter::once(t).chain(items).collect(), err));
         }
         Ok(())
     }
+
+    fn push<'a>(
+        &'a self,
+        mut inner: MutexGuard<'a, Inner<T>>,
+        t: T,
+    ) -> (MutexGuard<'a, Inner<T>>, Result<(), SendError<T>>) {
+        if inner.rejects_sends() {
+            return (inner, Err(SendError(t)));
+        }
+        while inner.is_full() {
+            match inner.policy {
+                OverflowPolicy::Block => {
+                    inner = self.shared.not_full.wait(inner).unwrap();
+                    if inner.rejects_sends() {
+                        return (inner, Err(SendError(t)));
+                    }
+                }
+                OverflowPolicy::DropNewest => return (inner, Err(SendError(t))),
+                OverflowPolicy::DropOldest => drop(inner.queue.pop_front()),
+            }
+        }
+        inner.queue.push_back(t);
+        match inner.notify {
+            NotifyStrategy::One => self.shared.condvar.notify_one(),
+            NotifyStrategy::All => self.shared.condvar.notify_all(),
+        }
+        inner.wake_tasks();
+        (inner, Ok(()))
+    }
+
+    /// Returns true once sends can no longer succeed: every receiver has been dropped, or
+    /// the channel was closed with `close_after_flush`.
+    pub fn is_closed(&self) -> bool {
+        self.shared.inner.lock().unwrap().rejects_sends()
+    }
+
+    /// Closes the channel for sending without losing what's already queued. From now on
+    /// every send, through any sender, fails and hands its item back, including sends
+    /// blocked on a full channel. Receivers still get every queued item, and only then see
+    /// the channel as disconnected, just as if all senders had been dropped.
+    pub fn close_after_flush(&self) {
+        let mut inner = self.shared.inner.lock().unwrap();
+        inner.accepting = false;
+        inner.disconnected = true;
+        self.shared.condvar.notify_all();
+        self.shared.not_full.notify_all();
+        inner.wake_tasks();
+    }
+
+    /// Blocks until the shared queue is empty or every receiver is gone.
+    ///
+    /// Empty means the receivers have taken every item, not that they've finished with
+    /// them: a buffered receiver moves the whole queue into its private buffer at once, so
+    /// this can return while that buffer still holds unprocessed items.
+    pub fn block_until_empty(&self) {
+        let mut inner = self.shared.inner.lock().unwrap();
+        while !inner.queue.is_empty() && inner.receivers > 0 {
+            inner = self.shared.empty.wait(inner).unwrap();
+        }
+    }
 }
 
 impl<T> Receiver<T> {
+    #[allow(clippy::mut_from_ref)]
     fn get_buffer(&self) -> &mut VecDeque<T> {
-        // Safety:
+        // Safety: Receiver is !Sync and the returned reference never outlives a single method call
         unsafe { &mut *self.buffer.get() }
     }
 
+    fn take(&self, inner: &mut Inner<T>) -> Option<T> {
+        let t = inner.queue.pop_front()?;
+        if inner.buffered {
+            std::mem::swap(self.get_buffer(), &mut inner.queue);
+        }
+        if inner.queue.is_empty() {
+            self.shared.empty.notify_all();
+        }
+        if inner.capacity.is_some() {
+            if inner.buffered {
+                self.shared.not_full.notify_all();
+            } else {
+                self.shared.not_full.notify_one();
+            }
+        }
+        Some(t)
+    }
+
     pub fn recv(&self) -> Result<T, RecvError> {
         if let Some(t) = self.get_buffer().pop_front() {
             return Ok(t);
@@ -134,9 +490,8 @@ impl<T> Receiver<T> {
 
         let mut inner = self.shared.inner.lock().unwrap();
         loop {
-            match inner.queue.pop_front() {
+            match self.take(&mut inner) {
                 Some(t) => {
-                    std::mem::swap(self.get_buffer(), &mut inner.queue);
                     return Ok(t);
                 }
                 None if inner.disconnected => {
@@ -149,27 +504,197 @@ impl<T> Receiver<T> {
         }
     }
 
-    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+    /// Like `recv_timeout`, but against an absolute deadline, so one deadline can be
+    /// shared by several receive calls.
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
         if let Some(t) = self.get_buffer().pop_front() {
             return Ok(t);
         }
 
         let mut inner = self.shared.inner.lock().unwrap();
+        loop {
+            match self.take(&mut inner) {
+                Some(t) => return Ok(t),
+                None if inner.disconnected => return Err(RecvTimeoutError::Disconnected),
+                None => {
+                    // rechecked after every wakeup, spurious or not
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    if left.is_zero() {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    inner = self.shared.condvar.wait_timeout(inner, left).unwrap().0;
+                }
+            }
+        }
+    }
+
+    /// Like `recv`, but returns `Timeout` once `dur` has passed without an item. The wait is
+    /// measured against a single deadline, so spurious wakeups don't extend it.
+    pub fn recv_timeout(&self, dur: Duration) -> Result<T, RecvTimeoutError> {
+        match Instant::now().checked_add(dur) {
+            Some(deadline) => self.recv_deadline(deadline),
+            None => self
+                .recv()
+                .map_err(|RecvError| RecvTimeoutError::Disconnected),
+        }
+    }
+
+    /// Receives until an item matches `pred`, waiting at most `dur` in total. Items that
+    /// don't match are discarded, not re-queued, so other receivers never see them.
+    pub fn recv_timeout_while<F>(&self, dur: Duration, mut pred: F) -> Result<T, RecvTimeoutError>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // one deadline for the whole call, so skipped items don't extend the wait
+        let deadline = Instant::now().checked_add(dur);
+        loop {
+            let t = match deadline {
+                Some(deadline) => self.recv_deadline(deadline)?,
+                None => self
+                    .recv()
+                    .map_err(|RecvError| RecvTimeoutError::Disconnected)?,
+            };
+            if pred(&t) {
+                return Ok(t);
+            }
+        }
+    }
 
-        match inner.queue.pop_front() {
-            Some(t) => {
-                std::mem::swap(self.get_buffer(), &mut inner.queue);
-                Ok(t)
+    /// Collects up to `max` items, returning early with whatever arrived (maybe nothing)
+    /// once `dur` has passed since the call, or once the senders are gone.
+    pub fn recv_batch_timeout(&self, max: usize, dur: Duration) -> Vec<T> {
+        // one deadline for the whole batch, not one per item
+        let deadline = Instant::now().checked_add(dur);
+        let mut batch = Vec::new();
+        while batch.len() < max {
+            let res = match deadline {
+                Some(deadline) => self.recv_deadline(deadline),
+                None => self
+                    .recv()
+                    .map_err(|RecvError| RecvTimeoutError::Disconnected),
+            };
+            match res {
+                Ok(t) => batch.push(t),
+                Err(_) => break,
             }
+        }
+        batch
+    }
+
+    /// Returns true once all senders have been dropped, even if items are still queued.
+    pub fn is_closed(&self) -> bool {
+        self.shared.inner.lock().unwrap().disconnected
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        if let Some(t) = self.get_buffer().pop_front() {
+            return Ok(t);
+        }
+
+        let mut inner = self.shared.inner.lock().unwrap();
+
+        match self.take(&mut inner) {
+            Some(t) => Ok(t),
             None if inner.disconnected => Err(TryRecvError::Disconnected),
             None => Err(TryRecvError::Empty),
         }
     }
+
+    /// Forks this receiver in two, giving each half of the items already moved into our
+    /// private buffer. Both keep receiving from the shared channel afterwards.
+    pub fn split_buffer(self) -> (Receiver<T>, Receiver<T>) {
+        let other = self.clone();
+        let half = self.get_buffer().len() / 2;
+        *other.get_buffer() = self.get_buffer().split_off(half);
+        (self, other)
+    }
+
+    /// Takes everything available right now, our private buffer first, in FIFO order.
+    /// Never blocks, and locks the channel only once.
+    pub fn drain(&self) -> Vec<T> {
+        let buffer = self.get_buffer();
+        let mut inner = self.shared.inner.lock().unwrap();
+        let mut items = Vec::with_capacity(buffer.len() + inner.queue.len());
+        items.extend(buffer.drain(..));
+        items.extend(inner.queue.drain(..));
+        self.shared.empty.notify_all();
+        if inner.capacity.is_some() {
+            self.shared.not_full.notify_all();
+        }
+        items
+    }
+
+    /// Like `try_recv`, but as a single three-state value for hand-written polling loops.
+    /// Items still buffered after the senders left are reported as `Message` before `Closed`.
+    pub fn recv_status(&self) -> RecvStatus<T> {
+        match self.try_recv() {
+            Ok(t) => RecvStatus::Message(t),
+            Err(TryRecvError::Empty) => RecvStatus::WouldBlock,
+            Err(TryRecvError::Disconnected) => RecvStatus::Closed,
+        }
+    }
+
+    /// Blocking iterator over borrowed `self`; like `into_iter`, but the receiver stays
+    /// usable once the loop ends.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self)
+    }
+
+    /// Non-blocking iterator that yields whatever is available right now and stops at the
+    /// first `try_recv` error, whether the channel is empty or disconnected.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        TryIter(self)
+    }
+}
+
+/// Blocking iterator that borrows the receiver, see `Receiver::iter`.
+pub struct Iter<'a, T>(&'a Receiver<T>);
+
+impl<T> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.recv().ok()
+    }
+}
+
+/// Non-blocking iterator that borrows the receiver, see `Receiver::try_iter`.
+pub struct TryIter<'a, T>(&'a Receiver<T>);
+
+impl<T> Iterator for TryIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        // try_recv empties the private buffer before it locks
+        self.0.try_recv().ok()
+    }
+}
+
+/// Blocking iterator that owns the receiver, see `IntoIterator for Receiver`.
+pub struct IntoIter<T>(Receiver<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.recv().ok()
+    }
+}
+
+/// `for t in rx` receives until every sender is gone and the queue is drained.
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::thread;
 
     #[test]
@@ -193,10 +718,7 @@ mod tests {
 
         handle.join().unwrap();
 
-        let mut results = vec![];
-        results.push(rx.recv().unwrap());
-        results.push(rx.recv().unwrap());
-        results.push(rx.recv().unwrap());
+        let mut results = vec![rx.recv().unwrap(), rx.recv().unwrap(), rx.recv().unwrap()];
         results.sort();
         assert_eq!(results, vec![1, 2, 3]);
     }
@@ -244,8 +766,617 @@ mod tests {
         let mut count = 0;
         while let Ok(num) = rx.try_recv() {
             count += 1;
-            assert!(num >= 0 && num < 100);
+            assert!((0..100).contains(&num));
         }
         assert_eq!(count, 10 * 100);
     }
+
+    #[test]
+    fn overflow_block() {
+        let (tx, rx) = sync_channel_with_policy(2, OverflowPolicy::Block);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+
+        let handle = thread::spawn(move || {
+            tx.send(3).unwrap(); // blocks until the receiver makes room
+        });
+
+        thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        assert_eq!(rx.recv().unwrap(), 1);
+        handle.join().unwrap();
+        assert_eq!(rx.recv().unwrap(), 2);
+        assert_eq!(rx.recv().unwrap(), 3);
+    }
+
+    #[test]
+    fn sync_channel_blocks_when_full() {
+        let (tx, rx) = sync_channel(3);
+        let sent = Arc::new(AtomicUsize::new(0));
+        let handle = {
+            let sent = Arc::clone(&sent);
+            thread::spawn(move || {
+                for i in 0..4 {
+                    tx.send(i).unwrap();
+                    sent.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        };
+
+        thread::sleep(std::time::Duration::from_millis(50));
+        assert_eq!(sent.load(Ordering::SeqCst), 3); // stuck on the fourth send
+        assert_eq!(rx.recv().unwrap(), 0);
+        handle.join().unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 4);
+        assert_eq!(rx.drain(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn try_send() {
+        let (tx, rx) = sync_channel(1);
+        assert!(tx.try_send(String::from("a")).is_ok());
+        match tx.try_send(String::from("b")) {
+            Err(TrySendError::Full(t)) => assert_eq!(t, "b"),
+            other => panic!("expected Full, got {other:?}"),
+        }
+
+        assert_eq!(rx.recv().unwrap(), "a");
+        assert!(tx.try_send(String::from("c")).is_ok());
+        drop(rx);
+        match tx.try_send(String::from("d")) {
+            Err(TrySendError::Disconnected(t)) => assert_eq!(t, "d"),
+            other => panic!("expected Disconnected, got {other:?}"),
+        }
+
+        // an unbounded channel is never full
+        let (tx, _rx) = channel();
+        for i in 0..100 {
+            assert!(tx.try_send(i).is_ok());
+        }
+    }
+
+    #[test]
+    fn overflow_drop_newest() {
+        let (tx, rx) = sync_channel_with_policy(2, OverflowPolicy::DropNewest);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert!(matches!(tx.send(3), Err(SendError(3))));
+
+        assert_eq!(rx.try_recv().unwrap(), 1);
+        assert_eq!(rx.try_recv().unwrap(), 2);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+    }
+
+    #[test]
+    fn overflow_drop_oldest() {
+        let (tx, rx) = sync_channel_with_policy(2, OverflowPolicy::DropOldest);
+        for i in 1..=5 {
+            tx.send(i).unwrap();
+        }
+
+        assert_eq!(rx.try_recv().unwrap(), 4);
+        assert_eq!(rx.try_recv().unwrap(), 5);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+    }
+
+    #[test]
+    fn sender_is_closed() {
+        let (tx, rx) = channel::<i32>();
+        assert!(!tx.is_closed());
+        drop(rx);
+        assert!(tx.is_closed());
+    }
+
+    #[test]
+    fn receiver_is_closed() {
+        let (tx, rx) = channel();
+        let tx_clone = tx.clone();
+        tx.send(1).unwrap();
+        drop(tx);
+        assert!(!rx.is_closed());
+        drop(tx_clone);
+        assert!(rx.is_closed());
+        assert_eq!(rx.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn send_after_receiver_drop() {
+        let (tx, rx) = channel();
+        tx.send(1).unwrap();
+        drop(rx);
+        assert!(matches!(tx.send(2), Err(SendError(2))));
+    }
+
+    #[test]
+    fn send_after_consumer_panics() {
+        let (tx, rx) = channel();
+        let item = Arc::new(());
+        tx.send(Arc::clone(&item)).unwrap();
+
+        let consumer = thread::spawn(move || {
+            let _rx = rx;
+            panic!("consumer died before reading anything");
+        });
+        assert!(consumer.join().is_err());
+
+        // the queued item was freed with the last receiver instead of piling up
+        assert_eq!(Arc::strong_count(&item), 1);
+        match tx.send(Arc::clone(&item)) {
+            Err(SendError(t)) => assert!(Arc::ptr_eq(&t, &item)),
+            Ok(()) => panic!("send succeeded with no receivers"),
+        }
+    }
+
+    #[test]
+    fn blocked_send_wakes_on_receiver_drop() {
+        let (tx, rx) = sync_channel_with_policy(1, OverflowPolicy::Block);
+        tx.send(1).unwrap();
+
+        let handle = thread::spawn(move || tx.send(2));
+
+        thread::sleep(std::time::Duration::from_millis(50));
+        drop(rx);
+        assert!(matches!(handle.join().unwrap(), Err(SendError(2))));
+    }
+
+    #[test]
+    fn close_after_flush() {
+        let (tx, rx) = sync_channel(3);
+        tx.send_all([1, 2, 3]).unwrap();
+        let blocked = {
+            let tx = tx.clone();
+            thread::spawn(move || tx.send(4))
+        };
+        thread::sleep(Duration::from_millis(50));
+
+        tx.close_after_flush();
+        assert!(matches!(blocked.join().unwrap(), Err(SendError(4))));
+        let other = tx.clone();
+        assert!(matches!(
+            thread::spawn(move || other.send(5)).join().unwrap(),
+            Err(SendError(5))
+        ));
+        assert!(tx.is_closed());
+
+        // a live sender doesn't keep the receiver waiting
+        assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(rx.is_closed());
+        assert!(matches!(rx.recv(), Err(RecvError)));
+        drop(tx);
+    }
+
+    #[test]
+    fn send_all() {
+        let (tx, rx) = channel();
+        tx.send_all(0..5).unwrap();
+        for i in 0..5 {
+            assert_eq!(rx.recv().unwrap(), i);
+        }
+
+        let (tx, rx) = sync_channel_with_policy(2, OverflowPolicy::DropNewest);
+        let err = tx.send_all(0..5).unwrap_err();
+        assert_eq!(err.0, vec![2, 3, 4]);
+        assert_eq!(rx.try_recv().unwrap(), 0);
+        assert_eq!(rx.try_recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn try_send_all() {
+        let (tx, rx) = sync_channel(3);
+        match tx.try_send_all(1..=5) {
+            Err((rest, TrySendError::Full(()))) => assert_eq!(rest, [4, 5]),
+            other => panic!("expected Full, got {other:?}"),
+        }
+        assert_eq!(rx.drain(), [1, 2, 3]);
+
+        assert!(tx.try_send_all([4, 5]).is_ok());
+        drop(rx);
+        match tx.try_send_all([6, 7]) {
+            Err((rest, TrySendError::Disconnected(()))) => assert_eq!(rest, [6, 7]),
+            other => panic!("expected Disconnected, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cloned_receivers() {
+        let (tx, rx) = channel();
+        let rx1 = rx.clone();
+        for i in 0..4 {
+            tx.send(i).unwrap();
+        }
+        // rx pulls the whole queue into its private buffer
+        assert_eq!(rx.recv().unwrap(), 0);
+        assert!(matches!(rx1.try_recv(), Err(TryRecvError::Empty)));
+
+        // dropping rx hands its backlog to rx1
+        drop(rx);
+        assert_eq!(rx1.recv().unwrap(), 1);
+        assert_eq!(rx1.recv().unwrap(), 2);
+        assert_eq!(rx1.recv().unwrap(), 3);
+        assert!(!tx.is_closed());
+        drop(rx1);
+        assert!(tx.is_closed());
+    }
+
+    #[test]
+    fn into_iter() {
+        let (tx, rx) = channel();
+        let handle = thread::spawn(move || {
+            for i in 1..=3 {
+                tx.send(i).unwrap();
+            }
+        });
+        let items: Vec<_> = rx.into_iter().collect();
+        assert_eq!(items, [1, 2, 3]);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn iter() {
+        let (tx, rx) = channel();
+        tx.send_all([1, 2, 3]).unwrap();
+        let first: Vec<_> = rx.iter().take(2).collect();
+        assert_eq!(first, [1, 2]);
+        assert_eq!(rx.recv().unwrap(), 3);
+
+        tx.send(4).unwrap();
+        drop(tx);
+        assert_eq!(rx.iter().collect::<Vec<_>>(), [4]);
+    }
+
+    #[test]
+    fn try_iter() {
+        let (tx, rx) = channel();
+        tx.send_all([1, 2, 3]).unwrap();
+        // the channel stays open, so a blocking iterator would hang here
+        assert_eq!(rx.try_iter().count(), 3);
+        assert_eq!(rx.try_iter().next(), None);
+
+        tx.send(4).unwrap();
+        drop(tx);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [4]);
+    }
+
+    #[test]
+    fn handback_wakes_async_waiters() {
+        use crate::select::{Either, select2};
+
+        let (tx, rx) = channel();
+        let rx2 = rx.clone();
+        let (_tx_other, rx_other) = channel::<()>();
+        tx.send_all([1, 2, 3]).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1); // rx buffers 2 and 3 privately
+
+        // parks on rx2 through the waker list, not the condvar
+        let waiter = thread::spawn(move || select2(&rx2, &rx_other).unwrap());
+        thread::sleep(Duration::from_millis(20));
+        drop(rx);
+        assert_eq!(waiter.join().unwrap(), Either::Left(2));
+    }
+
+    #[test]
+    fn recv_status() {
+        let (tx, rx) = channel();
+        assert_eq!(rx.recv_status(), RecvStatus::WouldBlock);
+
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        drop(tx);
+        assert_eq!(rx.recv_status(), RecvStatus::Message(1));
+        assert_eq!(rx.recv_status(), RecvStatus::Message(2));
+        assert_eq!(rx.recv_status(), RecvStatus::Closed);
+    }
+
+    #[test]
+    fn notify_strategies() {
+        use std::sync::Barrier;
+
+        for notify in [NotifyStrategy::One, NotifyStrategy::All] {
+            // bounded, so each recv takes a single item instead of the whole queue
+            let (tx, rx) = ChannelBuilder::new().capacity(4).notify(notify).build();
+            let done = Arc::new(Barrier::new(3));
+
+            // both receivers are parked before anything is sent, and the second send finds
+            // the queue non-empty; it must still wake the other receiver
+            let handles: Vec<_> = [rx.clone(), rx]
+                .into_iter()
+                .map(|rx| {
+                    let done = Arc::clone(&done);
+                    thread::spawn(move || {
+                        let t = rx.recv().unwrap();
+                        done.wait(); // keep rx alive until both have received
+                        t
+                    })
+                })
+                .collect();
+            thread::sleep(std::time::Duration::from_millis(50));
+            tx.send(1).unwrap();
+            tx.send(2).unwrap();
+            done.wait();
+
+            let mut got: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+            got.sort();
+            assert_eq!(got, vec![1, 2]);
+        }
+    }
+
+    #[test]
+    fn builder() {
+        let (tx, rx) = ChannelBuilder::new()
+            .capacity(2)
+            .overflow(OverflowPolicy::DropOldest)
+            .notify(NotifyStrategy::All)
+            .build();
+        for i in 0..4 {
+            tx.send(i).unwrap();
+        }
+        assert_eq!(rx.try_recv().unwrap(), 2);
+        assert_eq!(rx.try_recv().unwrap(), 3);
+
+        // unbuffered: a receiver takes one item per lock, leaving the rest to its clones
+        let (tx, rx) = ChannelBuilder::new().buffered(false).build();
+        let rx1 = rx.clone();
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+        assert_eq!(rx1.try_recv().unwrap(), 2);
+
+        // buffered bounded: one receive drains the shared queue and frees every slot
+        let (tx, rx) = ChannelBuilder::new().capacity(2).buffered(true).build();
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+        tx.send(3).unwrap();
+        tx.send(4).unwrap();
+        assert_eq!(rx.recv().unwrap(), 2);
+    }
+
+    #[test]
+    fn recv_timeout() {
+        let (tx, rx) = channel::<i32>();
+        let start = Instant::now();
+        assert!(
+            rx.recv_timeout(Duration::from_millis(50))
+                .unwrap_err()
+                .is_timeout()
+        );
+        let waited = start.elapsed();
+        assert!(waited >= Duration::from_millis(50));
+        assert!(waited < Duration::from_secs(1));
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx.send(1).unwrap();
+        });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 1);
+        handle.join().unwrap();
+        assert!(
+            rx.recv_timeout(Duration::from_secs(5))
+                .unwrap_err()
+                .is_disconnected()
+        );
+    }
+
+    #[test]
+    fn recv_deadline() {
+        let (tx, rx) = channel();
+        let past = Instant::now() - Duration::from_millis(10);
+        let start = Instant::now();
+        assert!(rx.recv_deadline(past).unwrap_err().is_timeout());
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        // a queued item still wins over an expired deadline
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv_deadline(past).unwrap(), 1);
+
+        let deadline = Instant::now() + Duration::from_millis(30);
+        assert!(rx.recv_deadline(deadline).unwrap_err().is_timeout());
+        assert!(Instant::now() >= deadline);
+    }
+
+    #[test]
+    fn recv_timeout_while() {
+        let (tx, rx) = channel();
+        let handle = thread::spawn(move || {
+            for i in [1, 3, 4, 5] {
+                thread::sleep(Duration::from_millis(10));
+                tx.send(i).unwrap();
+            }
+            tx
+        });
+        let even = rx.recv_timeout_while(Duration::from_secs(5), |x| x % 2 == 0);
+        assert_eq!(even.unwrap(), 4);
+        assert_eq!(rx.recv().unwrap(), 5);
+
+        // a steady stream of non-matching items must not stretch the deadline
+        let tx = handle.join().unwrap();
+        let feeder = thread::spawn(move || {
+            for _ in 0..40 {
+                if tx.send(1).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+        let start = Instant::now();
+        let res = rx.recv_timeout_while(Duration::from_millis(50), |x| x % 2 == 0);
+        assert!(matches!(res, Err(RecvTimeoutError::Timeout)));
+        assert!(start.elapsed() < Duration::from_millis(150));
+        drop(rx);
+        feeder.join().unwrap();
+
+        let (tx, rx) = channel::<i32>();
+        drop(tx);
+        let res = rx.recv_timeout_while(Duration::from_secs(5), |_| true);
+        assert!(matches!(res, Err(RecvTimeoutError::Disconnected)));
+    }
+
+    #[test]
+    fn drain() {
+        let (tx, rx) = channel();
+        for i in 0..50 {
+            tx.send(i).unwrap();
+        }
+        // pull one through recv so part of the backlog sits in the private buffer
+        assert_eq!(rx.recv().unwrap(), 0);
+        tx.send(50).unwrap();
+        drop(tx);
+
+        assert_eq!(rx.drain(), (1..=50).collect::<Vec<_>>());
+        assert!(rx.drain().is_empty());
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+    }
+
+    #[test]
+    fn error_predicates() {
+        assert!(TryRecvError::Empty.is_empty());
+        assert!(!TryRecvError::Empty.is_disconnected());
+        assert!(TryRecvError::Disconnected.is_disconnected());
+        assert!(!TryRecvError::Disconnected.is_empty());
+
+        assert!(RecvTimeoutError::Timeout.is_timeout());
+        assert!(!RecvTimeoutError::Timeout.is_disconnected());
+        assert!(RecvTimeoutError::Disconnected.is_disconnected());
+        assert!(!RecvTimeoutError::Disconnected.is_timeout());
+
+        let (tx, rx) = channel::<i32>();
+        assert!(rx.try_recv().unwrap_err().is_empty());
+        drop(tx);
+        assert!(rx.try_recv().unwrap_err().is_disconnected());
+    }
+
+    #[test]
+    fn split_buffer() {
+        let (tx, rx) = channel();
+        for i in 0..100 {
+            tx.send(i).unwrap();
+        }
+        // the first recv moves the rest of the backlog into the private buffer
+        let mut seen = vec![rx.recv().unwrap()];
+        let (a, b) = rx.split_buffer();
+        for i in 100..200 {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+
+        let handles: Vec<_> = [a, b]
+            .into_iter()
+            .map(|rx| {
+                thread::spawn(move || {
+                    let mut got = Vec::new();
+                    while let Ok(x) = rx.recv() {
+                        got.push(x);
+                    }
+                    got
+                })
+            })
+            .collect();
+        for handle in handles {
+            let got = handle.join().unwrap();
+            assert!(got.is_sorted()); // each half keeps FIFO order
+            seen.extend(got);
+        }
+        seen.sort();
+        assert_eq!(seen, (0..200).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn recv_drains_after_disconnect() {
+        let (tx, rx) = channel();
+        let tx2 = tx.clone();
+        for i in 0..10 {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+        drop(tx2);
+
+        for i in 0..10 {
+            assert_eq!(rx.recv().unwrap(), i);
+        }
+        assert!(matches!(rx.recv(), Err(RecvError)));
+    }
+
+    #[test]
+    fn try_recv_drains_after_disconnect() {
+        // half the items sit in the private buffer and half in the shared queue
+        let (tx, rx) = channel();
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+        assert_eq!(rx.try_recv().unwrap(), 0);
+        for i in 5..10 {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+
+        for i in 1..10 {
+            assert_eq!(rx.try_recv().unwrap(), i);
+        }
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+        assert!(matches!(rx.recv(), Err(RecvError)));
+    }
+
+    #[test]
+    fn block_until_empty() {
+        use std::time::Duration;
+
+        let (tx, rx) = ChannelBuilder::new().buffered(false).build();
+        for i in 0..10 {
+            tx.send(i).unwrap();
+        }
+        let consumer = thread::spawn(move || {
+            while let Ok(_x) = rx.recv() {
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+
+        let start = Instant::now();
+        tx.block_until_empty();
+        assert!(tx.shared.inner.lock().unwrap().queue.is_empty());
+        // the consumer needs ~5ms per item, so most of them must have been waited for
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        drop(tx);
+        consumer.join().unwrap();
+
+        // returns straight away once nobody is left to drain the queue
+        let (tx, rx) = channel();
+        tx.send(1).unwrap();
+        drop(rx);
+        tx.block_until_empty();
+    }
+
+    #[test]
+    fn recv_batch_timeout() {
+        use std::time::Duration;
+
+        let (tx, rx) = channel();
+        for i in 0..10 {
+            tx.send(i).unwrap();
+        }
+        let start = Instant::now();
+        assert_eq!(
+            rx.recv_batch_timeout(4, Duration::from_secs(5)),
+            [0, 1, 2, 3]
+        );
+        assert!(start.elapsed() < Duration::from_secs(1)); // full batch, no waiting
+
+        let sender = thread::spawn(move || {
+            tx.send(10).unwrap();
+            thread::sleep(Duration::from_millis(20));
+            tx.send(11).unwrap();
+            thread::sleep(Duration::from_millis(500));
+            let _ = tx.send(12);
+        });
+        let start = Instant::now();
+        let batch = rx.recv_batch_timeout(100, Duration::from_millis(100));
+        let elapsed = start.elapsed();
+        assert_eq!(batch, (4..12).collect::<Vec<_>>());
+        assert!(elapsed >= Duration::from_millis(100));
+        assert!(elapsed < Duration::from_millis(400), "took {elapsed:?}");
+
+        assert!(rx.recv_batch_timeout(0, Duration::from_secs(5)).is_empty());
+        drop(rx);
+        sender.join().unwrap();
+    }
 }
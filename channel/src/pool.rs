@@ -0,0 +1,79 @@
+use std::thread::{self, JoinHandle};
+
+use crate::{OverflowPolicy, Sender, sync_channel_with_policy};
+
+pub type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Fixed set of threads pulling jobs from one bounded channel. `submit` blocks once
+/// `capacity` jobs are waiting, so a fast producer can't queue unbounded work.
+pub struct WorkerPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    pub fn new(n_threads: usize) -> Self {
+        Self::with_capacity(n_threads, n_threads)
+    }
+
+    pub fn with_capacity(n_threads: usize, capacity: usize) -> Self {
+        assert!(n_threads > 0, "worker pool needs at least one thread");
+        // bounded channels hand out one item per recv, so idle workers can't hoard jobs
+        let (sender, receiver) = sync_channel_with_policy::<Job>(capacity, OverflowPolicy::Block);
+        let workers = (0..n_threads)
+            .map(|_| {
+                let receiver = receiver.clone();
+                thread::spawn(move || {
+                    while let Ok(job) = receiver.recv() {
+                        job();
+                    }
+                })
+            })
+            .collect();
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if every worker has exited, which only happens when jobs panicked.
+    pub fn submit(&self, job: impl FnOnce() + Send + 'static) {
+        let sender = self.sender.as_ref().unwrap();
+        if sender.send(Box::new(job)).is_err() {
+            panic!("all workers have exited");
+        }
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        // closing the channel lets workers finish the queued jobs and then exit
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn it_works() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let pool = WorkerPool::new(4);
+        for _ in 0..100 {
+            let counter = Arc::clone(&counter);
+            pool.submit(move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+        drop(pool);
+        assert_eq!(counter.load(Ordering::Relaxed), 100);
+    }
+}
@@ -0,0 +1,233 @@
+use std::sync::Arc;
+use std::task::{Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use crate::{Receiver, RecvError, TryRecvError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+const MAX_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Waits for whichever of `a` and `b` produces an item first, by polling both and parking
+/// for a growing interval in between. `a` is polled first, so it wins when both are ready.
+/// Returns `Err(RecvError)` once both are disconnected and drained.
+///
+/// Polling keeps this independent of the channels' condvars, at the cost of up to
+/// `MAX_BACKOFF` of extra latency while idle.
+pub fn try_recv_either<A, B>(a: &Receiver<A>, b: &Receiver<B>) -> Result<Either<A, B>, RecvError> {
+    let mut backoff = Duration::from_micros(1);
+    loop {
+        let a_closed = match a.try_recv() {
+            Ok(t) => return Ok(Either::Left(t)),
+            Err(e) => e.is_disconnected(),
+        };
+        match b.try_recv() {
+            Ok(t) => return Ok(Either::Right(t)),
+            Err(TryRecvError::Disconnected) if a_closed => return Err(RecvError),
+            Err(_) => {}
+        }
+        thread::park_timeout(backoff);
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Blocks until whichever of `a` and `b` produces an item first, and returns it.
+/// Returns `Err(RecvError)` once both are disconnected and drained.
+///
+/// Unlike `try_recv_either` this doesn't poll: the calling thread registers a waker with
+/// both channels, the same hook async receivers use, and parks until a send or disconnect
+/// on either one unparks it.
+///
+/// Fairness: `a` is always checked first, so when both have items `a` wins, and a
+/// receiver `a` that never runs dry starves `b`. Swap the arguments between calls if
+/// both sides need to make progress.
+pub fn select2<A, B>(a: &Receiver<A>, b: &Receiver<B>) -> Result<Either<A, B>, RecvError> {
+    wait(|waker| {
+        let a_closed = match recv_or_register(a, waker) {
+            Ok(t) => return Some(Ok(Either::Left(t))),
+            Err(e) => e.is_disconnected(),
+        };
+        match recv_or_register(b, waker) {
+            Ok(t) => Some(Ok(Either::Right(t))),
+            Err(TryRecvError::Disconnected) if a_closed => Some(Err(RecvError)),
+            Err(_) => None,
+        }
+    })
+}
+
+/// `select2` for any number of receivers of the same item type: blocks until one of them
+/// produces an item and returns it with that receiver's index. Returns `Err(RecvError)`
+/// once all of them are disconnected and drained, or right away for an empty slice.
+///
+/// Fairness: receivers are checked in slice order, so the lowest ready index wins, and a
+/// busy receiver starves the ones after it. Rotate the slice between calls if every
+/// channel needs to make progress.
+pub fn select<T>(receivers: &[&Receiver<T>]) -> Result<(usize, T), RecvError> {
+    wait(|waker| {
+        let mut all_closed = true;
+        for (i, rx) in receivers.iter().enumerate() {
+            match recv_or_register(rx, waker) {
+                Ok(t) => return Some(Ok((i, t))),
+                Err(e) => all_closed &= e.is_disconnected(),
+            }
+        }
+        all_closed.then_some(Err(RecvError))
+    })
+}
+
+// the wait loop behind select and select2: `poll` tries each receiver once, leaving the
+// thread's waker on the empty ones, and returns None if we should park and try again
+fn wait<R>(mut poll: impl FnMut(&Waker) -> Option<Result<R, RecvError>>) -> Result<R, RecvError> {
+    let waker = THREAD_WAKER.with(Waker::clone);
+    loop {
+        if let Some(res) = poll(&waker) {
+            return res;
+        }
+        // the waker we left on the other receivers may unpark us early later on; we just loop
+        thread::park();
+    }
+}
+
+// one waker per thread, so will_wake dedups it across select calls and a channel that
+// stays idle holds at most one entry for us instead of one per call
+thread_local! {
+    static THREAD_WAKER: Waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// try_recv that, when the channel is empty, leaves `waker` to be woken by the next send.
+// Registering under the lock a sender needs to push means no wakeup is lost.
+fn recv_or_register<T>(rx: &Receiver<T>, waker: &Waker) -> Result<T, TryRecvError> {
+    if let Some(t) = rx.get_buffer().pop_front() {
+        return Ok(t);
+    }
+
+    let mut inner = rx.shared.inner.lock().unwrap();
+    match rx.take(&mut inner) {
+        Some(t) => Ok(t),
+        None if inner.disconnected => Err(TryRecvError::Disconnected),
+        None => {
+            if !inner.wakers.iter().any(|w| w.will_wake(waker)) {
+                inner.wakers.push(waker.clone());
+            }
+            Err(TryRecvError::Empty)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel;
+
+    #[test]
+    fn either_side() {
+        let (tx_a, rx_a) = channel::<i32>();
+        let (tx_b, rx_b) = channel::<&str>();
+
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            tx_b.send("b").unwrap();
+            thread::sleep(Duration::from_millis(10));
+            tx_a.send(1).unwrap();
+        });
+        assert_eq!(try_recv_either(&rx_a, &rx_b).unwrap(), Either::Right("b"));
+        assert_eq!(try_recv_either(&rx_a, &rx_b).unwrap(), Either::Left(1));
+        sender.join().unwrap();
+        assert!(try_recv_either(&rx_a, &rx_b).is_err()); // both senders gone
+    }
+
+    #[test]
+    fn one_side_closed() {
+        let (tx_a, rx_a) = channel::<i32>();
+        let (tx_b, rx_b) = channel::<i32>();
+        drop(tx_a);
+        tx_b.send(2).unwrap();
+        assert_eq!(try_recv_either(&rx_a, &rx_b).unwrap(), Either::Right(2));
+        drop(tx_b);
+        assert!(try_recv_either(&rx_a, &rx_b).is_err());
+    }
+
+    #[test]
+    fn select2_one_side() {
+        let (tx_a, rx_a) = channel::<i32>();
+        let (tx_b, rx_b) = channel::<&str>();
+
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx_b.send("b").unwrap();
+            tx_b
+        });
+        assert_eq!(select2(&rx_a, &rx_b).unwrap(), Either::Right("b"));
+        let tx_b = sender.join().unwrap();
+
+        tx_a.send(1).unwrap();
+        tx_b.send("b").unwrap();
+        assert_eq!(select2(&rx_a, &rx_b).unwrap(), Either::Left(1)); // a goes first
+        assert_eq!(select2(&rx_a, &rx_b).unwrap(), Either::Right("b"));
+
+        drop(tx_a);
+        let closer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(tx_b);
+        });
+        assert!(select2(&rx_a, &rx_b).is_err()); // woken by the disconnect
+        closer.join().unwrap();
+    }
+
+    #[test]
+    fn select2_doesnt_pile_up_wakers() {
+        let (_tx_a, rx_a) = channel::<i32>();
+        let (tx_b, rx_b) = channel::<i32>();
+        for i in 0..100 {
+            let rx_b = &rx_b;
+            let tx_b = tx_b.clone();
+            thread::scope(|s| {
+                s.spawn(move || {
+                    thread::sleep(Duration::from_millis(1));
+                    tx_b.send(i).unwrap();
+                });
+                assert_eq!(select2(&rx_a, rx_b).unwrap(), Either::Right(i));
+            });
+        }
+        // `a` never sent, so its single registration from this thread is all that's left
+        assert_eq!(rx_a.shared.inner.lock().unwrap().wakers.len(), 1);
+    }
+
+    #[test]
+    fn select_many() {
+        let channels: Vec<_> = (0..3).map(|_| channel::<i32>()).collect();
+        let receivers: Vec<_> = channels.iter().map(|(_, rx)| rx).collect();
+
+        let tx = channels[1].0.clone();
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx.send(7).unwrap();
+        });
+        assert_eq!(select(&receivers).unwrap(), (1, 7));
+        sender.join().unwrap();
+
+        channels[2].0.send(8).unwrap();
+        channels[0].0.send(9).unwrap();
+        assert_eq!(select(&receivers).unwrap(), (0, 9)); // lowest index first
+        assert_eq!(select(&receivers).unwrap(), (2, 8));
+
+        let (senders, receivers): (Vec<_>, Vec<_>) = channels.into_iter().unzip();
+        drop(senders);
+        let receivers: Vec<_> = receivers.iter().collect();
+        assert!(select(&receivers).is_err());
+        assert!(select::<i32>(&[]).is_err());
+    }
+}
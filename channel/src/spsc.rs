@@ -0,0 +1,239 @@
+use std::cell::{Cell, UnsafeCell};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{RecvError, SendError, TryRecvError};
+
+// Single-producer single-consumer channel over a fixed-size ring buffer.
+// `head` is only written by the receiver and `tail` only by the sender, so no lock is needed:
+// the slots in [head, tail) belong to the receiver, the rest to the sender.
+// head and tail count up freely and wrap at usize::MAX; the buffer length is a power of two
+// so masking maps them to the same slot before and after the wrap.
+
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0, "channel capacity must be non-zero");
+    let slots = cap
+        .checked_next_power_of_two()
+        .expect("channel capacity too large");
+    let shared = Arc::new(Shared {
+        buffer: (0..slots)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        cap,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        sender_alive: AtomicBool::new(true),
+        receiver_alive: AtomicBool::new(true),
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+            phantom: PhantomData,
+        },
+        Receiver {
+            shared,
+            phantom: PhantomData,
+        },
+    )
+}
+
+struct Shared<T> {
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>, // cap rounded up to a power of two
+    cap: usize,                                // at most this many items in flight
+    head: AtomicUsize, // next position to read, only advanced by the receiver
+    tail: AtomicUsize, // next position to write, only advanced by the sender
+    sender_alive: AtomicBool,
+    receiver_alive: AtomicBool,
+}
+
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn slot(&self, pos: usize) -> *mut MaybeUninit<T> {
+        self.buffer[pos & (self.buffer.len() - 1)].get()
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        let mut pos = head;
+        while pos != tail {
+            // SAFETY: slots in [head, tail) were written and never read
+            unsafe { (*self.slot(pos)).assume_init_drop() };
+            pos = pos.wrapping_add(1);
+        }
+    }
+}
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+    phantom: PhantomData<Cell<()>>, // !Sync, only one producer
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.sender_alive.store(false, Ordering::Release);
+    }
+}
+
+impl<T> Sender<T> {
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        let shared = &*self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        while tail.wrapping_sub(shared.head.load(Ordering::Acquire)) == shared.cap {
+            if !shared.receiver_alive.load(Ordering::Acquire) {
+                return Err(SendError(t));
+            }
+            backoff();
+        }
+        if !shared.receiver_alive.load(Ordering::Acquire) {
+            return Err(SendError(t));
+        }
+        // SAFETY: the slot at tail is outside [head, tail), so the receiver doesn't touch it
+        unsafe { (*shared.slot(tail)).write(t) };
+        shared.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    phantom: PhantomData<Cell<()>>, // !Sync, only one consumer
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+impl<T> Receiver<T> {
+    pub fn recv(&self) -> Result<T, RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(t) => return Ok(t),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => backoff(),
+            }
+        }
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let shared = &*self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        if head == shared.tail.load(Ordering::Acquire) {
+            if shared.sender_alive.load(Ordering::Acquire) {
+                return Err(TryRecvError::Empty);
+            }
+            // the sender may have pushed one last item right before it was dropped
+            if head == shared.tail.load(Ordering::Acquire) {
+                return Err(TryRecvError::Disconnected);
+            }
+        }
+        // SAFETY: head < tail, so the slot was written and the sender won't touch it until head moves
+        let t = unsafe { (*shared.slot(head)).assume_init_read() };
+        shared.head.store(head.wrapping_add(1), Ordering::Release);
+        Ok(t)
+    }
+}
+
+fn backoff() {
+    std::hint::spin_loop();
+    std::thread::yield_now();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn wraparound() {
+        let (tx, rx) = channel(4);
+        for round in 0..5 {
+            for i in 0..3 {
+                tx.send(round * 3 + i).unwrap();
+            }
+            for i in 0..3 {
+                assert_eq!(rx.try_recv().unwrap(), round * 3 + i);
+            }
+        }
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+    }
+
+    #[test]
+    fn counters_wrap() {
+        use std::sync::Arc;
+
+        // start just below the wrap with a capacity that isn't a power of two
+        let (tx, rx) = channel(3);
+        for counter in [&tx.shared.head, &tx.shared.tail] {
+            counter.store(usize::MAX - 4, Ordering::Relaxed);
+        }
+        for i in 0..9 {
+            tx.send(i).unwrap();
+            assert_eq!(rx.try_recv().unwrap(), i);
+        }
+
+        // pending items across the wrap are each dropped exactly once
+        let item = Arc::new(());
+        let (tx, rx) = channel(3);
+        for counter in [&tx.shared.head, &tx.shared.tail] {
+            counter.store(usize::MAX - 1, Ordering::Relaxed);
+        }
+        for _ in 0..3 {
+            tx.send(Arc::clone(&item)).unwrap();
+        }
+        assert!(tx.shared.tail.load(Ordering::Relaxed) < 2); // wrapped
+        drop((tx, rx));
+        assert_eq!(Arc::strong_count(&item), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "channel capacity too large")]
+    fn capacity_too_large() {
+        let _ = channel::<u8>(usize::MAX / 2 + 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "channel capacity must be non-zero")]
+    fn zero_capacity() {
+        let _ = channel::<u8>(0);
+    }
+
+    #[test]
+    fn threaded_in_order() {
+        let (tx, rx) = channel(8);
+        let handle = thread::spawn(move || {
+            for i in 0..10_000 {
+                tx.send(i).unwrap();
+            }
+        });
+        for i in 0..10_000 {
+            assert_eq!(rx.recv().unwrap(), i);
+        }
+        handle.join().unwrap();
+        assert!(matches!(rx.recv(), Err(RecvError)));
+    }
+
+    #[test]
+    fn disconnect() {
+        let (tx, rx) = channel(2);
+        tx.send(String::from("left behind")).unwrap();
+        drop(rx);
+        assert!(tx.send(String::from("x")).is_err());
+
+        let (tx, rx) = channel::<i32>(2);
+        drop(tx);
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+    }
+}
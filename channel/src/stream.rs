@@ -0,0 +1,100 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::Receiver;
+
+/// Adapts a `Receiver` into a `Stream` that ends once every sender is gone and the queue
+/// is drained.
+pub struct ReceiverStream<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T> ReceiverStream<T> {
+    pub fn new(receiver: Receiver<T>) -> Self {
+        Self { receiver }
+    }
+
+    pub fn into_inner(self) -> Receiver<T> {
+        self.receiver
+    }
+}
+
+impl<T> Stream for ReceiverStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let receiver = &self.receiver;
+        if let Some(t) = receiver.get_buffer().pop_front() {
+            return Poll::Ready(Some(t));
+        }
+
+        let mut inner = receiver.shared.inner.lock().unwrap();
+        match receiver.take(&mut inner) {
+            Some(t) => Poll::Ready(Some(t)),
+            None if inner.disconnected => Poll::Ready(None),
+            None => {
+                // registered under the same lock a sender needs to push, so no wakeup is lost
+                if !inner.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    inner.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel;
+    use std::future::{Future, poll_fn};
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+    use std::time::Duration;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = std::pin::pin!(fut);
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn collect_stream() {
+        let (tx, rx) = channel();
+        let mut stream = ReceiverStream::new(rx);
+
+        let handle = thread::spawn(move || {
+            for i in 0..10 {
+                tx.send(i).unwrap();
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+
+        let res = block_on(async {
+            let mut res = Vec::new();
+            while let Some(x) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+                res.push(x);
+            }
+            res
+        });
+        handle.join().unwrap();
+        assert_eq!(res, (0..10).collect::<Vec<_>>());
+    }
+}
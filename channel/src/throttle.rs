@@ -0,0 +1,121 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{SendError, Sender};
+
+/// Limits a `Sender` to `rate` messages per `per` with a token bucket.
+///
+/// The bucket holds at most `rate` tokens and starts full, so a burst of `rate` sends goes
+/// through immediately; after that tokens trickle back in continuously.
+pub struct Throttle<T> {
+    sender: Sender<T>,
+    rate: u32,
+    per: Duration,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+#[derive(Debug)]
+pub enum ThrottleError<T> {
+    /// No token is available right now.
+    Throttled(T),
+    Disconnected(T),
+}
+
+impl<T> Throttle<T> {
+    pub fn new(sender: Sender<T>, rate: u32, per: Duration) -> Self {
+        assert!(rate > 0, "rate must be non-zero");
+        assert!(!per.is_zero(), "period must be non-zero");
+        Self {
+            sender,
+            rate,
+            per,
+            tokens: rate as f64,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Sends `t` if the budget allows it, otherwise hands it back without waiting.
+    pub fn try_send_throttled(&mut self, t: T) -> Result<(), ThrottleError<T>> {
+        self.refill();
+        if self.tokens < 1.0 {
+            return Err(ThrottleError::Throttled(t));
+        }
+        self.tokens -= 1.0;
+        self.sender
+            .send(t)
+            .map_err(|SendError(t)| ThrottleError::Disconnected(t))
+    }
+
+    /// Sleeps until a token is available, then sends `t`.
+    pub fn send_throttled(&mut self, t: T) -> Result<(), SendError<T>> {
+        loop {
+            self.refill();
+            if self.tokens >= 1.0 {
+                break;
+            }
+            let missing = 1.0 - self.tokens;
+            thread::sleep(self.per.mul_f64(missing / self.rate as f64));
+        }
+        self.tokens -= 1.0;
+        self.sender.send(t)
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now - self.refilled_at;
+        self.refilled_at = now;
+        let earned = elapsed.as_secs_f64() / self.per.as_secs_f64() * self.rate as f64;
+        self.tokens = (self.tokens + earned).min(self.rate as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel;
+
+    #[test]
+    fn try_send_over_budget() {
+        let (tx, rx) = channel();
+        let mut tx = Throttle::new(tx, 2, Duration::from_secs(60));
+
+        tx.try_send_throttled(1).unwrap();
+        tx.try_send_throttled(2).unwrap();
+        assert!(matches!(
+            tx.try_send_throttled(3),
+            Err(ThrottleError::Throttled(3))
+        ));
+        assert_eq!(rx.recv().unwrap(), 1);
+        assert_eq!(rx.recv().unwrap(), 2);
+
+        drop(rx);
+        let (tx, rx) = channel();
+        let mut tx = Throttle::new(tx, 1, Duration::from_secs(60));
+        drop(rx);
+        assert!(matches!(
+            tx.try_send_throttled(1),
+            Err(ThrottleError::Disconnected(1))
+        ));
+    }
+
+    #[test]
+    fn send_throttled_rate() {
+        // 10 per 100ms rather than 10/sec keeps the test short; the math is the same
+        let (tx, rx) = channel();
+        let mut tx = Throttle::new(tx, 10, Duration::from_millis(100));
+
+        let start = Instant::now();
+        for i in 0..100 {
+            tx.send_throttled(i).unwrap();
+        }
+        let elapsed = start.elapsed();
+
+        // the first 10 are the initial burst, the other 90 need 900ms of refill
+        assert!(elapsed >= Duration::from_millis(850), "took {elapsed:?}");
+        assert!(elapsed < Duration::from_secs(3), "took {elapsed:?}");
+        drop(tx);
+        let received: Vec<_> = std::iter::from_fn(|| rx.recv().ok()).collect();
+        assert_eq!(received, (0..100).collect::<Vec<_>>());
+    }
+}
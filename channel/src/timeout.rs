@@ -0,0 +1,68 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{Receiver, RecvTimeoutError, channel};
+
+/// Forwards everything from `rx` as `Some(t)` on a helper thread. If nothing arrives for
+/// `dur` after the previous message, it sends a single `None` and stops, which is a simple
+/// way to notice a stalled producer. If the senders disconnect first, the returned
+/// receiver just ends without a `None`.
+pub fn with_timeout<T: Send + 'static>(rx: Receiver<T>, dur: Duration) -> Receiver<Option<T>> {
+    let (tx, out) = channel();
+    thread::spawn(move || {
+        loop {
+            let res = match Instant::now().checked_add(dur) {
+                Some(deadline) => rx.recv_deadline(deadline),
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            let forwarded = match res {
+                Ok(t) => tx.send(Some(t)),
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = tx.send(None);
+                    return;
+                }
+                Err(RecvTimeoutError::Disconnected) => return,
+            };
+            if forwarded.is_err() {
+                return; // nobody is listening anymore
+            }
+        }
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_producer() {
+        let (tx, rx) = channel();
+        let out = with_timeout(rx, Duration::from_millis(50));
+        let producer = thread::spawn(move || {
+            for i in 0..3 {
+                tx.send(i).unwrap();
+                thread::sleep(Duration::from_millis(10));
+            }
+            thread::sleep(Duration::from_millis(200)); // stall
+            let _ = tx.send(3);
+        });
+
+        for i in 0..3 {
+            assert_eq!(out.recv().unwrap(), Some(i));
+        }
+        assert_eq!(out.recv().unwrap(), None);
+        assert!(out.recv().is_err()); // stopped after the timeout signal
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn disconnect_ends_without_none() {
+        let (tx, rx) = channel();
+        let out = with_timeout(rx, Duration::from_secs(10));
+        tx.send(1).unwrap();
+        drop(tx);
+        assert_eq!(out.recv().unwrap(), Some(1));
+        assert!(out.recv().is_err());
+    }
+}
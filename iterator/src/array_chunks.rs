@@ -0,0 +1,138 @@
+use std::mem::MaybeUninit;
+
+// each chunk is written straight into an array, so items need neither Default nor Copy
+pub struct ArrayChunks<I: Iterator, const N: usize> {
+    iter: I,
+    remainder: Option<Vec<I::Item>>, // set once, when the iterator runs out
+}
+
+impl<I: Iterator, const N: usize> ArrayChunks<I, N> {
+    pub(crate) fn new(iter: I) -> Self {
+        assert!(N != 0, "chunk size must be non-zero");
+        Self {
+            iter,
+            remainder: None,
+        }
+    }
+
+    /// The trailing items that didn't fill a whole chunk; empty until the iterator is exhausted.
+    pub fn remainder(&self) -> &[I::Item] {
+        self.remainder.as_deref().unwrap_or(&[])
+    }
+
+    pub fn into_remainder(self) -> Vec<I::Item> {
+        self.remainder.unwrap_or_default()
+    }
+}
+
+// drops the filled prefix if the inner iterator panics halfway through a chunk
+struct Partial<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Partial<T, N> {
+    fn into_vec(mut self) -> Vec<T> {
+        let len = std::mem::take(&mut self.len);
+        // SAFETY: the first len slots are initialized, and len = 0 keeps Drop off them
+        self.buf[..len]
+            .iter()
+            .map(|slot| unsafe { slot.assume_init_read() })
+            .collect()
+    }
+}
+
+impl<T, const N: usize> Drop for Partial<T, N> {
+    fn drop(&mut self) {
+        for slot in &mut self.buf[..self.len] {
+            // SAFETY: the first len slots are initialized
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for ArrayChunks<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remainder.is_some() {
+            return None; // fused, so the leftover isn't overwritten
+        }
+        let mut chunk = Partial::<I::Item, N> {
+            buf: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        };
+        while chunk.len < N {
+            match self.iter.next() {
+                Some(item) => {
+                    chunk.buf[chunk.len].write(item);
+                    chunk.len += 1;
+                }
+                None => {
+                    self.remainder = Some(chunk.into_vec());
+                    return None;
+                }
+            }
+        }
+        chunk.len = 0; // the array takes ownership of all N items
+        // SAFETY: all N slots were written, and MaybeUninit<T> has the layout of T
+        Some(unsafe { std::mem::transmute_copy(&chunk.buf) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remainder.is_some() {
+            return (0, Some(0));
+        }
+        let (lo, hi) = self.iter.size_hint();
+        (lo / N, hi.map(|hi| hi / N))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn array_chunks() {
+        let mut chunks = (0..7).my_array_chunks::<3>();
+        assert_eq!(chunks.size_hint(), (2, Some(2)));
+        assert_eq!(chunks.next(), Some([0, 1, 2]));
+        assert_eq!(chunks.next(), Some([3, 4, 5]));
+        assert_eq!(chunks.remainder(), &[] as &[i32]);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), &[6]);
+
+        let words = ["a", "b", "c", "d"].map(String::from);
+        let pairs: Vec<[String; 2]> = words.into_iter().my_array_chunks().collect();
+        assert_eq!(pairs, [["a", "b"], ["c", "d"]]);
+    }
+
+    #[test]
+    fn remainder_survives_extra_next() {
+        let mut chunks = ["a", "b", "c"]
+            .map(String::from)
+            .into_iter()
+            .my_array_chunks::<2>();
+        assert!(chunks.next().is_some());
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.size_hint(), (0, Some(0)));
+        assert_eq!(chunks.into_remainder(), ["c"]);
+    }
+
+    #[test]
+    fn partial_chunk_dropped_on_panic() {
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let items = [Rc::clone(&item), Rc::clone(&item)];
+        let mut iter = items
+            .into_iter()
+            .chain(std::iter::from_fn(|| panic!("inner panicked")));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            iter.by_ref().my_array_chunks::<3>().next()
+        }));
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&item), 1); // the two taken items were dropped, not leaked
+    }
+}
@@ -0,0 +1,90 @@
+pub struct Chain<A, B> {
+    // fused: each side is dropped once exhausted
+    a: Option<A>,
+    b: Option<B>,
+}
+
+impl<A, B> Chain<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a: Some(a),
+            b: Some(b),
+        }
+    }
+}
+
+impl<A, B> Iterator for Chain<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(ref mut a) = self.a {
+            let next = a.next();
+            if next.is_some() {
+                return next;
+            }
+            self.a = None;
+        }
+        self.b.as_mut()?.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        let (b_lo, b_hi) = self.b.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        let hi = match (a_hi, b_hi) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (a_lo.saturating_add(b_lo), hi)
+    }
+}
+
+impl<A, B> DoubleEndedIterator for Chain<A, B>
+where
+    A: DoubleEndedIterator,
+    B: DoubleEndedIterator<Item = A::Item>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // from the back, b comes first
+        if let Some(ref mut b) = self.b {
+            let next = b.next_back();
+            if next.is_some() {
+                return next;
+            }
+            self.b = None;
+        }
+        self.a.as_mut()?.next_back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn it_works() {
+        let res: Vec<i32> = (0..3).my_chain(vec![3, 4]).collect();
+        assert_eq!(res, vec![0, 1, 2, 3, 4]);
+        assert_eq!((0..3).my_chain(vec![3, 4]).size_hint(), (5, Some(5)));
+    }
+
+    #[test]
+    fn reverse() {
+        let res: Vec<i32> = (0..3).my_chain(vec![3, 4]).rev().collect();
+        assert_eq!(res, vec![4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut iter = (0..2).my_chain(vec![2, 3]);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+}
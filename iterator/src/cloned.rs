@@ -0,0 +1,108 @@
+pub struct Copied<I> {
+    iter: I,
+}
+
+impl<I> Copied<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<'a, I, T> Iterator for Copied<I>
+where
+    I: Iterator<Item = &'a T>,
+    T: 'a + Copy,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I, T> DoubleEndedIterator for Copied<I>
+where
+    I: DoubleEndedIterator<Item = &'a T>,
+    T: 'a + Copy,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().copied()
+    }
+}
+
+impl<'a, I, T> ExactSizeIterator for Copied<I>
+where
+    I: ExactSizeIterator<Item = &'a T>,
+    T: 'a + Copy,
+{
+}
+
+pub struct Cloned<I> {
+    iter: I,
+}
+
+impl<I> Cloned<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<'a, I, T> Iterator for Cloned<I>
+where
+    I: Iterator<Item = &'a T>,
+    T: 'a + Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().cloned()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I, T> DoubleEndedIterator for Cloned<I>
+where
+    I: DoubleEndedIterator<Item = &'a T>,
+    T: 'a + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().cloned()
+    }
+}
+
+impl<'a, I, T> ExactSizeIterator for Cloned<I>
+where
+    I: ExactSizeIterator<Item = &'a T>,
+    T: 'a + Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn copied() {
+        let sum: i32 = [1, 2, 3].iter().my_copied().sum();
+        assert_eq!(sum, 6);
+
+        let iter = [1, 2, 3].iter().my_copied();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn cloned() {
+        let data = vec![String::from("a"), String::from("b")];
+        let res: Vec<String> = data.iter().my_cloned().collect();
+        assert_eq!(res, data);
+        assert_eq!(data.iter().my_cloned().size_hint(), (2, Some(2)));
+    }
+}
@@ -0,0 +1,61 @@
+// yields the first item of every run of consecutive items with equal keys
+pub struct DedupByKey<I, K, F> {
+    iter: I,
+    key: F,
+    last: Option<K>,
+}
+
+impl<I, K, F> DedupByKey<I, K, F> {
+    pub(crate) fn new(iter: I, key: F) -> Self {
+        Self {
+            iter,
+            key,
+            last: None,
+        }
+    }
+}
+
+impl<I, K, F> Iterator for DedupByKey<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            let key = (self.key)(&item);
+            if self.last.as_ref() != Some(&key) {
+                self.last = Some(key);
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        // a non-empty rest yields at least one item unless it all repeats the last key
+        let lo = if self.last.is_none() { lo.min(1) } else { 0 };
+        (lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn dedup_by_key() {
+        let res: Vec<_> = [(1, 'a'), (1, 'b'), (2, 'c')]
+            .into_iter()
+            .my_dedup_by_key(|&(n, _)| n)
+            .collect();
+        assert_eq!(res, [(1, 'a'), (2, 'c')]);
+
+        let words = ["a", "b", "cat", "dog", "o", "bird", "fish", "x"];
+        let res: Vec<_> = words.into_iter().my_dedup_by_key(|w| w.len()).collect();
+        assert_eq!(res, ["a", "cat", "o", "bird", "x"]);
+    }
+}
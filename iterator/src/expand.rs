@@ -0,0 +1,68 @@
+use std::array;
+
+// like flat_map over a closure returning [B; N], but the array lives inline instead of in a Vec
+pub struct Expand<I, F, B, const N: usize> {
+    iter: I,
+    f: F,
+    current: Option<array::IntoIter<B, N>>, // what's left of the last expanded item
+}
+
+impl<I, F, B, const N: usize> Expand<I, F, B, N> {
+    pub(crate) fn new(iter: I, f: F) -> Self {
+        Self {
+            iter,
+            f,
+            current: None,
+        }
+    }
+}
+
+impl<I, F, B, const N: usize> Iterator for Expand<I, F, B, N>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> [B; N],
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(b) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(b);
+            }
+            // a loop rather than one refill, so N = 0 doesn't end early on a non-empty iter
+            let item = self.iter.next()?;
+            self.current = Some((self.f)(item).into_iter());
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.current.as_ref().map_or(0, ExactSizeIterator::len);
+        let (lo, hi) = self.iter.size_hint();
+        let lo = lo.saturating_mul(N).saturating_add(left);
+        let hi = hi.and_then(|hi| hi.checked_mul(N)?.checked_add(left));
+        (lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn expand() {
+        let res: Vec<_> = [1, 2, 3].into_iter().my_expand(|n| [n, n * 10]).collect();
+        assert_eq!(res, [1, 10, 2, 20, 3, 30]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut it = (0..4).my_expand(|n| [n; 3]);
+        assert_eq!(it.size_hint(), (12, Some(12)));
+        it.next();
+        assert_eq!(it.size_hint(), (11, Some(11)));
+
+        let mut none = (0..4).my_expand(|_| [0u8; 0]);
+        assert_eq!(none.size_hint(), (0, Some(0)));
+        assert_eq!(none.next(), None);
+    }
+}
@@ -0,0 +1,68 @@
+pub struct FilterMap<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F> FilterMap<I, F> {
+    pub(crate) fn new(iter: I, f: F) -> Self {
+        Self { iter, f }
+    }
+}
+
+impl<B, I, F> Iterator for FilterMap<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> Option<B>,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(b) = (self.f)(self.iter.next()?) {
+                return Some(b);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // any element may be filtered out
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<B, I, F> DoubleEndedIterator for FilterMap<I, F>
+where
+    I: DoubleEndedIterator,
+    F: FnMut(I::Item) -> Option<B>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(b) = (self.f)(self.iter.next_back()?) {
+                return Some(b);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn it_works() {
+        let iter = ["1", "x", "3"]
+            .iter()
+            .my_filter_map(|s| s.parse::<i32>().ok());
+        assert_eq!(iter.size_hint(), (0, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn reverse() {
+        let res: Vec<i32> = (0..10)
+            .my_filter_map(|x| (x % 3 == 0).then_some(x * 10))
+            .rev()
+            .collect();
+        assert_eq!(res, vec![90, 60, 30, 0]);
+    }
+}
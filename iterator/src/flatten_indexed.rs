@@ -0,0 +1,52 @@
+// like Flatten, but tags each item with the position of the outer item it came from;
+// empty inner iterators still take up an index
+pub struct FlattenIndexed<I: Iterator<Item: IntoIterator>> {
+    outer: I,
+    inner: Option<<I::Item as IntoIterator>::IntoIter>,
+    next_index: usize, // index the next outer item will get
+}
+
+impl<I: Iterator<Item: IntoIterator>> FlattenIndexed<I> {
+    pub(crate) fn new(outer: I) -> Self {
+        Self {
+            outer,
+            inner: None,
+            next_index: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item: IntoIterator>> Iterator for FlattenIndexed<I> {
+    type Item = (usize, <I::Item as IntoIterator>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.inner.as_mut().and_then(Iterator::next) {
+                return Some((self.next_index - 1, item));
+            }
+            self.inner = Some(self.outer.next()?.into_iter());
+            self.next_index += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn flatten_indexed() {
+        let res: Vec<_> = vec![vec!['a', 'b'], vec!['c']]
+            .into_iter()
+            .my_flatten_indexed()
+            .collect();
+        assert_eq!(res, [(0, 'a'), (0, 'b'), (1, 'c')]);
+
+        let res: Vec<_> = vec![vec![], vec![1], vec![], vec![2, 3]]
+            .into_iter()
+            .my_flatten_indexed()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(res, [1, 3, 3]);
+    }
+}
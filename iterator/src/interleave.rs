@@ -0,0 +1,63 @@
+// alternates between the two iterators; once either runs out, the other finishes alone
+pub struct Interleave<I, J> {
+    a: I,
+    b: J,
+    b_next: bool, // whose turn it is
+}
+
+impl<I, J> Interleave<I, J> {
+    pub(crate) fn new(a: I, b: J) -> Self {
+        Self {
+            a,
+            b,
+            b_next: false,
+        }
+    }
+}
+
+impl<I, J> Iterator for Interleave<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.b_next = !self.b_next;
+        if self.b_next {
+            self.a.next().or_else(|| self.b.next())
+        } else {
+            self.b.next().or_else(|| self.a.next())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a.size_hint();
+        let (b_lo, b_hi) = self.b.size_hint();
+        let hi = match (a_hi, b_hi) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (a_lo.saturating_add(b_lo), hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn interleave() {
+        let res: Vec<_> = [1, 3, 5].into_iter().my_interleave([2, 4]).collect();
+        assert_eq!(res, [1, 2, 3, 4, 5]);
+
+        let res: Vec<_> = [1].into_iter().my_interleave([2, 4, 6, 8]).collect();
+        assert_eq!(res, [1, 2, 4, 6, 8]);
+
+        let res: Vec<_> = [1, 3, 5, 7].into_iter().my_interleave(None).collect();
+        assert_eq!(res, [1, 3, 5, 7]);
+
+        let iter = [1, 3].into_iter().my_interleave([2, 4, 6]);
+        assert_eq!(iter.size_hint(), (5, Some(5)));
+    }
+}
@@ -0,0 +1,64 @@
+use std::iter::Peekable;
+
+pub struct IntersperseWith<I: Iterator, G> {
+    iter: Peekable<I>,
+    separator: G,
+    needs_sep: bool,
+}
+
+impl<I: Iterator, G> IntersperseWith<I, G> {
+    pub(crate) fn new(iter: I, separator: G) -> Self {
+        Self {
+            iter: iter.peekable(),
+            separator,
+            needs_sep: false,
+        }
+    }
+}
+
+impl<I, G> Iterator for IntersperseWith<I, G>
+where
+    I: Iterator,
+    G: FnMut() -> I::Item,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // only generate a separator when another element follows it
+        if self.needs_sep && self.iter.peek().is_some() {
+            self.needs_sep = false;
+            Some((self.separator)())
+        } else {
+            self.needs_sep = true;
+            self.iter.next()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn it_works() {
+        let res: Vec<String> = vec!["a", "b", "c"]
+            .into_iter()
+            .map(String::from)
+            .my_intersperse_with(|| String::from("-"))
+            .collect();
+        assert_eq!(res, vec!["a", "-", "b", "-", "c"]);
+    }
+
+    #[test]
+    fn separator_count() {
+        let mut generated = 0;
+        let count = (0..3)
+            .my_intersperse_with(|| {
+                generated += 1;
+                -1
+            })
+            .count();
+        assert_eq!(count, 5);
+        assert_eq!(generated, 2);
+    }
+}
@@ -1,8 +1,222 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+mod array_chunks;
+mod chain;
+mod cloned;
+mod dedup;
+mod expand;
+mod filter_map;
+mod flatten_indexed;
+mod interleave;
+mod intersperse;
+mod pairwise;
+mod product;
+mod rchunks;
+mod repeat;
+mod running_sum;
+mod tuple_windows;
+mod window_sums;
+
+pub use array_chunks::ArrayChunks;
+pub use chain::Chain;
+pub use cloned::{Cloned, Copied};
+pub use dedup::DedupByKey;
+pub use expand::Expand;
+pub use filter_map::FilterMap;
+pub use flatten_indexed::FlattenIndexed;
+pub use interleave::Interleave;
+pub use intersperse::IntersperseWith;
+pub use pairwise::Pairwise;
+pub use product::Product;
+pub use rchunks::RChunks;
+pub use repeat::{RepeatN, my_repeat_n};
+pub use running_sum::RunningSum;
+pub use tuple_windows::TupleWindows2;
+pub use window_sums::WindowSums;
+
 pub trait IteratorExt: Iterator + Sized {
     // Sized is need
     fn my_flatten(self) -> Flatten<Self>
     where
         Self: Iterator<Item: IntoIterator>;
+
+    // flattens two levels at once; a runtime depth can't be expressed since every level
+    // changes the item type, so deeper nesting is spelled as repeated my_flatten calls
+    fn my_flatten2(self) -> Flatten2<Self>
+    where
+        Self: Iterator<Item: IntoIterator<Item: IntoIterator>>;
+
+    fn my_intersperse_with<G>(self, separator: G) -> IntersperseWith<Self, G>
+    where
+        G: FnMut() -> Self::Item;
+
+    fn my_copied<'a, T: 'a + Copy>(self) -> Copied<Self>
+    where
+        Self: Iterator<Item = &'a T>;
+
+    fn my_cloned<'a, T: 'a + Clone>(self) -> Cloned<Self>
+    where
+        Self: Iterator<Item = &'a T>;
+
+    fn my_chain<J>(self, other: J) -> Chain<Self, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>;
+
+    fn my_filter_map<B, F>(self, f: F) -> FilterMap<Self, F>
+    where
+        F: FnMut(Self::Item) -> Option<B>;
+
+    fn my_all<F>(&mut self, f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool;
+
+    fn my_any<F>(&mut self, f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool;
+
+    fn my_nth(&mut self, n: usize) -> Option<Self::Item>;
+
+    fn my_last(self) -> Option<Self::Item>;
+
+    fn my_max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord;
+
+    fn my_min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord;
+
+    // both extremes in one pass, with the same tie-breaking as my_min and my_max
+    fn my_min_max(self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Ord + Clone;
+
+    // my_max/my_min with a custom comparator, for items that aren't Ord such as floats.
+    // The comparator is all there is: with `|a, b| a.partial_cmp(b).unwrap_or(Equal)` a NaN
+    // ties with everything, so whether it wins depends on where it sits; use total_cmp to
+    // get a NaN-aware order.
+    fn my_max_by<F>(self, cmp: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering;
+
+    fn my_min_by<F>(self, cmp: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering;
+
+    fn my_eq<J>(self, other: J) -> bool
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: PartialEq;
+
+    fn my_cmp<J>(self, other: J) -> Ordering
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord;
+
+    // yields the prefix sums: [1, 2, 3] gives 1, 3, 6
+    fn my_running_sum(self) -> RunningSum<Self>
+    where
+        Self::Item: Copy + Add<Output = Self::Item> + Default;
+
+    // pipes every item into the channel, stopping at the first failed send
+    fn my_send_all<U>(self, tx: &channel::Sender<U>) -> Result<(), channel::SendError<U>>
+    where
+        Self: Iterator<Item = U>;
+
+    // consecutive pairs: [1, 2, 3] gives (1, 2), (2, 3)
+    fn my_tuple_windows(self) -> TupleWindows2<Self>
+    where
+        Self::Item: Clone;
+
+    // [T; N] chunks; a trailing partial chunk is dropped but kept in `remainder()`
+    fn my_array_chunks<const N: usize>(self) -> ArrayChunks<Self, N>;
+
+    // always consumes; adapters like Filter can't know their length up front
+    fn my_count(self) -> usize;
+
+    // O(1) for iterators that know their exact length, without consuming anything
+    fn my_len(&self) -> usize
+    where
+        Self: ExactSizeIterator;
+
+    // flattens one level, pairing each item with the index of its outer group
+    fn my_flatten_indexed(self) -> FlattenIndexed<Self>
+    where
+        Self: Iterator<Item: IntoIterator>;
+
+    // fold that also reports how many items it consumed
+    fn my_fold_counting<B, F>(self, init: B, f: F) -> (B, usize)
+    where
+        F: FnMut(B, Self::Item) -> B;
+
+    // sum of every sliding window of n items: [1, 2, 3, 4] with n = 2 gives 3, 5, 7
+    fn my_window_sums(self, n: usize) -> WindowSums<Self>
+    where
+        Self::Item: Copy + Add<Output = Self::Item> + Sub<Output = Self::Item>;
+
+    fn my_dedup_by_key<K, F>(self, key: F) -> DedupByKey<Self, K, F>
+    where
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K;
+
+    // alternates items from self and other, then finishes whichever is longer
+    fn my_interleave<J>(self, other: J) -> Interleave<Self, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>;
+
+    // fold into an accumulator borrowed mutably, handy for building a Vec or HashMap
+    fn my_fold_ref<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(&mut B, Self::Item);
+
+    // every (a, b) pair, with other restarted from a clone for each a
+    fn my_cartesian_product<J>(self, other: J) -> Product<Self, J::IntoIter>
+    where
+        J: IntoIterator<IntoIter: Clone>,
+        Self::Item: Clone;
+
+    // stops at the first error, leaving the iterator just past the failing item
+    fn my_try_for_each<E, F>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(Self::Item) -> Result<(), E>;
+
+    // like slice::rchunks: the first chunk holds the last N items
+    fn my_rchunks<const N: usize>(self) -> RChunks<Self, N>
+    where
+        Self: DoubleEndedIterator;
+
+    fn my_unzip3<A, B, C>(self) -> (Vec<A>, Vec<B>, Vec<C>)
+    where
+        Self: Iterator<Item = (A, B, C)>;
+
+    fn my_join(self, sep: &str) -> String
+    where
+        Self::Item: AsRef<str>;
+
+    // f over each consecutive pair: [1, 2, 4] with |a, b| b - a gives 1, 2
+    fn my_pairwise<B, F>(self, f: F) -> Pairwise<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> B,
+        Self::Item: Clone;
+
+    fn my_collect_chars(self) -> String
+    where
+        Self: Iterator<Item = char>;
+
+    // my_join without a separator
+    fn my_concat(self) -> String
+    where
+        Self::Item: AsRef<str>;
+
+    // None for an empty iterator
+    fn my_average(self) -> Option<f64>
+    where
+        Self::Item: Into<f64>;
+
+    fn my_expand<B, const N: usize, F>(self, f: F) -> Expand<Self, F, B, N>
+    where
+        F: FnMut(Self::Item) -> [B; N];
 }
 
 impl<T> IteratorExt for T
@@ -15,12 +229,396 @@ where
     {
         Flatten::new(self)
     }
+
+    fn my_flatten2(self) -> Flatten2<Self>
+    where
+        Self: Iterator<Item: IntoIterator<Item: IntoIterator>>,
+    {
+        Flatten::new(Flatten::new(self))
+    }
+
+    fn my_intersperse_with<G>(self, separator: G) -> IntersperseWith<Self, G>
+    where
+        G: FnMut() -> Self::Item,
+    {
+        IntersperseWith::new(self, separator)
+    }
+
+    fn my_copied<'a, U: 'a + Copy>(self) -> Copied<Self>
+    where
+        Self: Iterator<Item = &'a U>,
+    {
+        Copied::new(self)
+    }
+
+    fn my_cloned<'a, U: 'a + Clone>(self) -> Cloned<Self>
+    where
+        Self: Iterator<Item = &'a U>,
+    {
+        Cloned::new(self)
+    }
+
+    fn my_chain<J>(self, other: J) -> Chain<Self, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>,
+    {
+        Chain::new(self, other.into_iter())
+    }
+
+    fn my_filter_map<B, F>(self, f: F) -> FilterMap<Self, F>
+    where
+        F: FnMut(Self::Item) -> Option<B>,
+    {
+        FilterMap::new(self, f)
+    }
+
+    fn my_all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        for item in self {
+            if !f(item) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn my_any<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        for item in self {
+            if f(item) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn my_nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            self.next()?;
+        }
+        self.next()
+    }
+
+    fn my_last(self) -> Option<Self::Item> {
+        let mut last = None;
+        for item in self {
+            last = Some(item);
+        }
+        last
+    }
+
+    fn my_max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        // ties go to the later element, like std
+        self.reduce(|max, item| if item >= max { item } else { max })
+    }
+
+    fn my_min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        // ties go to the earlier element, like std
+        self.reduce(|min, item| if item < min { item } else { min })
+    }
+
+    fn my_max_by<F>(self, mut cmp: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        // same tie-breaking as my_max
+        self.reduce(|max, item| match cmp(&item, &max) {
+            Ordering::Less => max,
+            Ordering::Equal | Ordering::Greater => item,
+        })
+    }
+
+    fn my_min_by<F>(self, mut cmp: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        // same tie-breaking as my_min
+        self.reduce(|min, item| match cmp(&item, &min) {
+            Ordering::Less => item,
+            Ordering::Equal | Ordering::Greater => min,
+        })
+    }
+
+    fn my_min_max(mut self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Ord + Clone,
+    {
+        let first = self.next()?;
+        let (mut min, mut max) = (first.clone(), first);
+        // order each pair first, then only its smaller half can be a new min and only its
+        // larger half a new max: 3 comparisons per 2 items instead of 4
+        while let Some(a) = self.next() {
+            let Some(b) = self.next() else {
+                if a < min {
+                    min = a;
+                } else if a >= max {
+                    max = a;
+                }
+                break;
+            };
+            let (small, large) = if b < a { (b, a) } else { (a, b) };
+            if small < min {
+                min = small;
+            }
+            if large >= max {
+                max = large;
+            }
+        }
+        Some((min, max))
+    }
+
+    fn my_eq<J>(self, other: J) -> bool
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: PartialEq,
+    {
+        let mut other = other.into_iter();
+        for a in self {
+            match other.next() {
+                Some(b) if a == b => {}
+                _ => return false,
+            }
+        }
+        other.next().is_none()
+    }
+
+    fn my_cmp<J>(self, other: J) -> Ordering
+    where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord,
+    {
+        // lexicographic: the first differing element decides, otherwise the shorter one is less
+        let mut other = other.into_iter();
+        for a in self {
+            let Some(b) = other.next() else {
+                return Ordering::Greater;
+            };
+            match a.cmp(&b) {
+                Ordering::Equal => {}
+                ord => return ord,
+            }
+        }
+        if other.next().is_some() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    fn my_running_sum(self) -> RunningSum<Self>
+    where
+        Self::Item: Copy + Add<Output = Self::Item> + Default,
+    {
+        RunningSum::new(self)
+    }
+
+    fn my_send_all<U>(self, tx: &channel::Sender<U>) -> Result<(), channel::SendError<U>>
+    where
+        Self: Iterator<Item = U>,
+    {
+        for item in self {
+            tx.send(item)?;
+        }
+        Ok(())
+    }
+
+    fn my_tuple_windows(self) -> TupleWindows2<Self>
+    where
+        Self::Item: Clone,
+    {
+        TupleWindows2::new(self)
+    }
+
+    fn my_array_chunks<const N: usize>(self) -> ArrayChunks<Self, N> {
+        ArrayChunks::new(self)
+    }
+
+    fn my_count(self) -> usize {
+        self.fold(0, |count, _| count + 1)
+    }
+
+    fn my_len(&self) -> usize
+    where
+        Self: ExactSizeIterator,
+    {
+        let (lower, upper) = self.size_hint();
+        debug_assert_eq!(upper, Some(lower));
+        lower
+    }
+
+    fn my_flatten_indexed(self) -> FlattenIndexed<Self>
+    where
+        Self: Iterator<Item: IntoIterator>,
+    {
+        FlattenIndexed::new(self)
+    }
+
+    fn my_fold_counting<B, F>(self, init: B, mut f: F) -> (B, usize)
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.fold((init, 0), |(acc, count), item| (f(acc, item), count + 1))
+    }
+
+    fn my_window_sums(self, n: usize) -> WindowSums<Self>
+    where
+        Self::Item: Copy + Add<Output = Self::Item> + Sub<Output = Self::Item>,
+    {
+        WindowSums::new(self, n)
+    }
+
+    fn my_dedup_by_key<K, F>(self, key: F) -> DedupByKey<Self, K, F>
+    where
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        DedupByKey::new(self, key)
+    }
+
+    fn my_interleave<J>(self, other: J) -> Interleave<Self, J::IntoIter>
+    where
+        J: IntoIterator<Item = Self::Item>,
+    {
+        Interleave::new(self, other.into_iter())
+    }
+
+    fn my_fold_ref<B, F>(self, mut init: B, mut f: F) -> B
+    where
+        F: FnMut(&mut B, Self::Item),
+    {
+        for item in self {
+            f(&mut init, item);
+        }
+        init
+    }
+
+    fn my_cartesian_product<J>(self, other: J) -> Product<Self, J::IntoIter>
+    where
+        J: IntoIterator<IntoIter: Clone>,
+        Self::Item: Clone,
+    {
+        Product::new(self, other.into_iter())
+    }
+
+    fn my_try_for_each<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(Self::Item) -> Result<(), E>,
+    {
+        for item in self {
+            f(item)?;
+        }
+        Ok(())
+    }
+
+    fn my_rchunks<const N: usize>(self) -> RChunks<Self, N>
+    where
+        Self: DoubleEndedIterator,
+    {
+        RChunks::new(self)
+    }
+
+    fn my_unzip3<A, B, C>(self) -> (Vec<A>, Vec<B>, Vec<C>)
+    where
+        Self: Iterator<Item = (A, B, C)>,
+    {
+        let (lower, _) = self.size_hint();
+        let mut res = (
+            Vec::with_capacity(lower),
+            Vec::with_capacity(lower),
+            Vec::with_capacity(lower),
+        );
+        for (a, b, c) in self {
+            res.0.push(a);
+            res.1.push(b);
+            res.2.push(c);
+        }
+        res
+    }
+
+    fn my_join(mut self, sep: &str) -> String
+    where
+        Self::Item: AsRef<str>,
+    {
+        let Some(first) = self.next() else {
+            return String::new();
+        };
+        // a rough guess from the first item, so short joins don't keep reallocating
+        let (lower, _) = self.size_hint();
+        let first = first.as_ref();
+        let mut res = String::with_capacity(first.len() + lower * (first.len() + sep.len()));
+        res.push_str(first);
+        for item in self {
+            res.push_str(sep);
+            res.push_str(item.as_ref());
+        }
+        res
+    }
+
+    fn my_pairwise<B, F>(self, f: F) -> Pairwise<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> B,
+        Self::Item: Clone,
+    {
+        Pairwise::new(self, f)
+    }
+
+    fn my_collect_chars(self) -> String
+    where
+        Self: Iterator<Item = char>,
+    {
+        // at least one byte per char
+        let mut res = String::with_capacity(self.size_hint().0);
+        for c in self {
+            res.push(c);
+        }
+        res
+    }
+
+    fn my_concat(self) -> String
+    where
+        Self::Item: AsRef<str>,
+    {
+        self.my_join("")
+    }
+
+    fn my_average(self) -> Option<f64>
+    where
+        Self::Item: Into<f64>,
+    {
+        // incremental mean, so no running sum that could grow without bound
+        let mut mean = 0.0;
+        let mut n = 0usize;
+        for x in self {
+            n += 1;
+            mean += (x.into() - mean) / n as f64;
+        }
+        (n > 0).then_some(mean)
+    }
+
+    fn my_expand<B, const N: usize, F>(self, f: F) -> Expand<Self, F, B, N>
+    where
+        F: FnMut(Self::Item) -> [B; N],
+    {
+        Expand::new(self, f)
+    }
 }
 
 pub struct Flatten<I: Iterator<Item: IntoIterator>> {
     inner: FlattenCompat<I, <I::Item as IntoIterator>::IntoIter>,
 }
 
+pub type Flatten2<I> = Flatten<Flatten<I>>;
+
 impl<I: Iterator<Item: IntoIterator>> Flatten<I> {
     fn new(iter: I) -> Self {
         Self {
@@ -166,4 +764,337 @@ mod tests {
         assert_eq!(iter0.next(), iter1.next());
         assert_eq!(iter0.next_back(), iter1.next_back());
     }
+
+    #[test]
+    fn flatten_depth() {
+        let data = vec![vec![vec![1, 2], vec![3]], vec![vec![4, 5, 6]]];
+
+        let depth1: Vec<Vec<i32>> = data.clone().into_iter().my_flatten().collect();
+        assert_eq!(depth1, vec![vec![1, 2], vec![3], vec![4, 5, 6]]);
+
+        let depth2: Vec<i32> = data.clone().into_iter().my_flatten2().collect();
+        assert_eq!(depth2, vec![1, 2, 3, 4, 5, 6]);
+
+        let rev: Vec<i32> = data.into_iter().my_flatten2().rev().collect();
+        assert_eq!(rev, vec![6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn all_any() {
+        assert!((0..5).my_all(|x| x < 5));
+        assert!(!(0..5).my_any(|x| x > 5));
+        assert!(std::iter::empty::<i32>().my_all(|_| false));
+        assert!(!std::iter::empty::<i32>().my_any(|_| true));
+    }
+
+    #[test]
+    fn all_any_short_circuit() {
+        let mut iter = 1..10;
+        assert!(iter.my_any(|x| x == 3));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![4, 5, 6, 7, 8, 9]);
+
+        let mut iter = 1..10;
+        assert!(!iter.my_all(|x| x < 3));
+        assert_eq!(iter.next(), Some(4));
+    }
+
+    #[test]
+    fn nth() {
+        let mut iter = 0..5;
+        assert_eq!(iter.my_nth(0), Some(0));
+        assert_eq!(iter.my_nth(2), Some(3));
+        assert_eq!(iter.my_nth(5), None);
+        assert_eq!(iter.next(), None); // exhausted by the overshoot
+    }
+
+    #[test]
+    fn last() {
+        assert_eq!((0..5).my_last(), Some(4));
+        assert_eq!(std::iter::empty::<i32>().my_last(), None);
+    }
+
+    #[test]
+    fn max_min() {
+        let data = [3, 1, 4, 1, 5, 9, 2, 6];
+        assert_eq!(data.iter().my_max(), Some(&9));
+        assert_eq!(data.iter().my_min(), Some(&1));
+        assert_eq!(std::iter::empty::<i32>().my_max(), None);
+        assert_eq!(std::iter::empty::<i32>().my_min(), None);
+    }
+
+    #[test]
+    fn max_min_ties() {
+        // compare by key only, so equal keys are told apart by the payload
+        #[derive(Debug, PartialEq, Eq)]
+        struct Tagged(i32, char);
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Tagged {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let data = || {
+            vec![
+                Tagged(1, 'a'),
+                Tagged(2, 'b'),
+                Tagged(1, 'c'),
+                Tagged(2, 'd'),
+            ]
+        };
+        assert_eq!(data().into_iter().my_max(), Some(Tagged(2, 'd')));
+        assert_eq!(data().into_iter().my_min(), Some(Tagged(1, 'a')));
+    }
+
+    #[test]
+    fn eq() {
+        assert!([1, 2, 3].iter().my_eq(&[1, 2, 3]));
+        assert!(![1, 2, 3].iter().my_eq(&[1, 2]));
+        assert!(![1, 2].iter().my_eq(&[1, 2, 3]));
+        assert!(![1, 2, 3].iter().my_eq(&[1, 2, 4]));
+        assert!(std::iter::empty::<i32>().my_eq(vec![]));
+    }
+
+    #[test]
+    fn cmp() {
+        assert_eq!([1, 2, 3].iter().my_cmp(&[1, 2, 4]), Ordering::Less);
+        assert_eq!([1, 2, 4].iter().my_cmp(&[1, 2, 3]), Ordering::Greater);
+        assert_eq!([1, 2].iter().my_cmp(&[1, 2, 3]), Ordering::Less);
+        assert_eq!([1, 2, 3].iter().my_cmp(&[1, 2]), Ordering::Greater);
+        assert_eq!([1, 2, 3].iter().my_cmp(&[1, 2, 3]), Ordering::Equal);
+    }
+
+    #[test]
+    fn send_all() {
+        let (tx, rx) = channel::channel();
+        let handle = std::thread::spawn(move || {
+            let mut res = Vec::new();
+            while let Ok(x) = rx.recv() {
+                res.push(x);
+            }
+            res
+        });
+        (0..100).my_send_all(&tx).unwrap();
+        drop(tx);
+        assert_eq!(handle.join().unwrap(), (0..100).collect::<Vec<_>>());
+
+        let (tx, rx) = channel::channel();
+        drop(rx);
+        assert_eq!([1, 2, 3].into_iter().my_send_all(&tx).unwrap_err().0, 1);
+    }
+
+    #[test]
+    fn count_len() {
+        let mut seen = 0;
+        let evens = (0..10).filter(|x| {
+            seen += 1;
+            x % 2 == 0
+        });
+        assert_eq!(evens.my_count(), 5);
+        assert_eq!(seen, 10); // every item went through the filter
+
+        let mut calls = 0;
+        let squares = (0..1000).map(|x| {
+            calls += 1;
+            x * x
+        });
+        assert_eq!(squares.my_len(), 1000);
+        drop(squares);
+        assert_eq!(calls, 0); // len came from the range, map never ran
+    }
+
+    #[test]
+    fn fold_counting() {
+        let (sum, count) = (1..=10)
+            .filter(|x| x % 3 == 0)
+            .my_fold_counting(0, |acc, x| acc + x);
+        assert_eq!((sum, count), (18, 3));
+
+        let (s, count) = std::iter::empty::<&str>().my_fold_counting(String::new(), |acc, _| acc);
+        assert_eq!((s.as_str(), count), ("", 0));
+    }
+
+    #[test]
+    fn min_max() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct Counted(i32);
+
+        impl PartialOrd for Counted {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Counted {
+            fn cmp(&self, other: &Self) -> Ordering {
+                COMPARISONS.with(|c| c.set(c.get() + 1));
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let data = [3, 1, 4, 1, 5, 9, 2, 6];
+        let res = data.into_iter().map(Counted).my_min_max();
+        assert_eq!(res, Some((Counted(1), Counted(9))));
+        assert!(COMPARISONS.with(Cell::get) < 2 * data.len());
+
+        assert_eq!(std::iter::empty::<i32>().my_min_max(), None);
+        assert_eq!([7].into_iter().my_min_max(), Some((7, 7)));
+        assert_eq!([2, 1].into_iter().my_min_max(), Some((1, 2)));
+
+        // ties: first minimum, last maximum, matching my_min and my_max
+        #[derive(Debug, Clone)]
+        struct Key(i32, char);
+
+        impl PartialEq for Key {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for Key {}
+
+        impl PartialOrd for Key {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Key {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        for len in 1..=6 {
+            let keys: Vec<_> = [1, 0, 1, 0, 1, 0]
+                .into_iter()
+                .zip('a'..)
+                .map(|(k, c)| Key(k, c))
+                .take(len)
+                .collect();
+            let (min, max) = keys.iter().cloned().my_min_max().unwrap();
+            assert_eq!(min.1, keys.iter().cloned().my_min().unwrap().1);
+            assert_eq!(max.1, keys.iter().cloned().my_max().unwrap().1);
+        }
+    }
+
+    #[test]
+    fn fold_ref() {
+        use std::collections::HashMap;
+
+        let text = "the cat and the hat and the bat";
+        let freq = text.split(' ').my_fold_ref(HashMap::new(), |map, word| {
+            *map.entry(word).or_insert(0) += 1
+        });
+        assert_eq!(freq["the"], 3);
+        assert_eq!(freq["and"], 2);
+        assert_eq!(freq["cat"], 1);
+        assert_eq!(freq.len(), 5);
+    }
+
+    #[test]
+    fn try_for_each() {
+        let mut sink = Vec::new();
+        let mut write = |x: i32| {
+            if sink.len() == 3 {
+                return Err(x);
+            }
+            sink.push(x);
+            Ok(())
+        };
+
+        let mut iter = 1..=6;
+        assert_eq!(iter.my_try_for_each(&mut write), Err(4));
+        assert_eq!(iter.collect::<Vec<_>>(), [5, 6]); // 4 was consumed by the failing call
+        assert_eq!(sink, [1, 2, 3]);
+
+        let mut iter = [1, 2].into_iter();
+        assert_eq!(iter.my_try_for_each(|_| Ok::<_, ()>(())), Ok(()));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn unzip3() {
+        let (nums, chars, flags) = vec![(1, 'a', true), (2, 'b', false)]
+            .into_iter()
+            .my_unzip3();
+        assert_eq!(nums, [1, 2]);
+        assert_eq!(chars, ['a', 'b']);
+        assert_eq!(flags, [true, false]);
+
+        let (a, b, c): (Vec<i32>, Vec<i32>, Vec<i32>) = std::iter::empty().my_unzip3();
+        assert!(a.is_empty() && b.is_empty() && c.is_empty());
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(["a", "b", "c"].iter().my_join("-"), "a-b-c");
+        assert_eq!(["only"].iter().my_join(", "), "only");
+        assert_eq!(std::iter::empty::<&str>().my_join(", "), "");
+
+        let owned = vec![String::from("x"), String::new(), String::from("yz")];
+        assert_eq!(owned.into_iter().my_join("::"), "x::::yz");
+    }
+
+    #[test]
+    fn collect_chars() {
+        assert_eq!("hello".chars().rev().my_collect_chars(), "olleh");
+        assert_eq!(['ä', '→', 'x'].into_iter().my_collect_chars(), "ä→x");
+        assert_eq!(std::iter::empty().my_collect_chars(), "");
+    }
+
+    #[test]
+    fn concat() {
+        assert_eq!(["ab", "", "cd"].iter().my_concat(), "abcd");
+        let owned = vec![String::from("x"), String::from("y")];
+        assert_eq!(owned.into_iter().my_concat(), "xy");
+        assert_eq!(std::iter::empty::<&str>().my_concat(), "");
+    }
+
+    #[test]
+    fn average() {
+        assert_eq!([1, 2, 3, 4].into_iter().my_average(), Some(2.5));
+        assert_eq!(std::iter::empty::<f64>().my_average(), None);
+        // summing these as u8 would overflow after two items
+        assert_eq!(std::iter::repeat_n(200u8, 10_000).my_average(), Some(200.0));
+    }
+
+    #[test]
+    fn max_by_min_by() {
+        let by_partial = |a: &f64, b: &f64| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let floats = [1.5, 3.2, 2.1];
+        assert_eq!(floats.into_iter().my_max_by(by_partial), Some(3.2));
+        assert_eq!(floats.into_iter().my_min_by(by_partial), Some(1.5));
+        assert_eq!(std::iter::empty().my_max_by(by_partial), None);
+
+        // ties: max keeps the last, min the first
+        let pairs = [(1, 'a'), (2, 'b'), (2, 'c'), (1, 'd')];
+        assert_eq!(
+            pairs.into_iter().my_max_by(|a, b| a.0.cmp(&b.0)),
+            Some((2, 'c'))
+        );
+        assert_eq!(
+            pairs.into_iter().my_min_by(|a, b| a.0.cmp(&b.0)),
+            Some((1, 'a'))
+        );
+
+        // a NaN ties with everything under partial_cmp, so position decides
+        let max = [1.5, 3.2, f64::NAN].into_iter().my_max_by(by_partial);
+        assert!(max.unwrap().is_nan());
+        let max = [1.5, f64::NAN, 3.2].into_iter().my_max_by(by_partial);
+        assert_eq!(max, Some(3.2));
+        // total_cmp puts a positive NaN above every number
+        let max = [1.5, f64::NAN, 3.2].into_iter().my_max_by(f64::total_cmp);
+        assert!(max.unwrap().is_nan());
+    }
 }
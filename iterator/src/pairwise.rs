@@ -0,0 +1,66 @@
+use crate::TupleWindows2;
+
+// applies f to each consecutive pair; the windowing, laziness included, is TupleWindows2's
+pub struct Pairwise<I: Iterator, F> {
+    windows: TupleWindows2<I>,
+    f: F,
+}
+
+impl<I: Iterator, F> Pairwise<I, F> {
+    pub(crate) fn new(iter: I, f: F) -> Self {
+        Self {
+            windows: TupleWindows2::new(iter),
+            f,
+        }
+    }
+}
+
+impl<B, I, F> Iterator for Pairwise<I, F>
+where
+    I: Iterator<Item: Clone>,
+    F: FnMut(&I::Item, &I::Item) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (prev, next) = self.windows.next()?;
+        Some((self.f)(&prev, &next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn differences() {
+        let res: Vec<_> = [1, 2, 4, 7].into_iter().my_pairwise(|a, b| b - a).collect();
+        assert_eq!(res, [1, 2, 3]);
+        assert_eq!([1].into_iter().my_pairwise(|a, b| b - a).next(), None);
+    }
+
+    #[test]
+    fn ratios() {
+        let res: Vec<_> = [1.0, 2.0, 3.0, 1.5]
+            .into_iter()
+            .my_pairwise(|a, b| b / a)
+            .collect();
+        assert_eq!(res, [2.0, 1.5, 0.5]);
+    }
+
+    #[test]
+    fn lazy() {
+        let (tx, rx) = channel::channel();
+        // nothing has been sent yet, so pulling an item here would block forever
+        let mut sums = rx.iter().my_pairwise(|a: &i32, b| a + b);
+        tx.send_all([1, 2, 3]).unwrap();
+        drop(tx);
+        assert_eq!(sums.next(), Some(3));
+        assert_eq!(sums.next(), Some(5));
+        assert_eq!(sums.next(), None);
+    }
+}
@@ -0,0 +1,60 @@
+// pairs each item of the outer iterator with every item of a fresh clone of `other`
+pub struct Product<I: Iterator, J> {
+    outer: I,
+    other: J,
+    current: Option<(I::Item, J)>, // the outer item and what's left of other for it
+}
+
+impl<I: Iterator, J> Product<I, J> {
+    pub(crate) fn new(outer: I, other: J) -> Self {
+        Self {
+            outer,
+            other,
+            current: None,
+        }
+    }
+}
+
+impl<I, J> Iterator for Product<I, J>
+where
+    I: Iterator<Item: Clone>,
+    J: Iterator + Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((a, rest)) = &mut self.current
+                && let Some(b) = rest.next()
+            {
+                return Some((a.clone(), b));
+            }
+            let a = self.outer.next()?;
+            self.current = Some((a, self.other.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn cartesian_product() {
+        let res: Vec<_> = [1, 2]
+            .into_iter()
+            .my_cartesian_product(['x', 'y'])
+            .collect();
+        assert_eq!(res, [(1, 'x'), (1, 'y'), (2, 'x'), (2, 'y')]);
+
+        assert_eq!(
+            [1, 2].into_iter().my_cartesian_product(None::<char>).next(),
+            None
+        );
+        assert_eq!(
+            std::iter::empty::<i32>().my_cartesian_product(0..3).next(),
+            None
+        );
+        assert_eq!((0..3).my_cartesian_product(0..4).count(), 12);
+    }
+}
@@ -0,0 +1,49 @@
+// chunks taken from the back, each in forward order; the last one yielded may be short
+pub struct RChunks<I, const N: usize> {
+    iter: I,
+}
+
+impl<I, const N: usize> RChunks<I, N> {
+    pub(crate) fn new(iter: I) -> Self {
+        assert!(N != 0, "chunk size must be non-zero");
+        Self { iter }
+    }
+}
+
+impl<I: DoubleEndedIterator, const N: usize> Iterator for RChunks<I, N> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk: Vec<_> = self.iter.by_ref().rev().take(N).collect();
+        if chunk.is_empty() {
+            return None;
+        }
+        chunk.reverse();
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        (lo.div_ceil(N), hi.map(|hi| hi.div_ceil(N)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn rchunks() {
+        let res: Vec<_> = (0..7).my_rchunks::<3>().collect();
+        assert_eq!(res, [vec![4, 5, 6], vec![1, 2, 3], vec![0]]);
+
+        let data = [1, 2, 3, 4, 5, 6];
+        let expect: Vec<_> = data.rchunks(2).map(<[_]>::to_vec).collect();
+        assert_eq!(
+            data.into_iter().my_rchunks::<2>().collect::<Vec<_>>(),
+            expect
+        );
+        assert_eq!((0..7).my_rchunks::<3>().size_hint(), (3, Some(3)));
+        assert_eq!((0..0).my_rchunks::<3>().next(), None);
+    }
+}
@@ -0,0 +1,67 @@
+pub struct RepeatN<T> {
+    item: Option<T>,
+    count: usize,
+}
+
+pub fn my_repeat_n<T: Clone>(item: T, n: usize) -> RepeatN<T> {
+    RepeatN {
+        item: if n == 0 { None } else { Some(item) },
+        count: n,
+    }
+}
+
+impl<T: Clone> Iterator for RepeatN<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+        self.count -= 1;
+        if self.count == 0 {
+            // the last one can be moved out instead of cloned
+            self.item.take()
+        } else {
+            self.item.clone()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count, Some(self.count))
+    }
+}
+
+impl<T: Clone> DoubleEndedIterator for RepeatN<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // every element is the same, so both ends behave identically
+        self.next()
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for RepeatN<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut iter = my_repeat_n(String::from("a"), 3);
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next().as_deref(), Some("a"));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back().as_deref(), Some("a"));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next().as_deref(), Some("a"));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn both_directions() {
+        assert_eq!(my_repeat_n(7, 5).count(), 5);
+        assert_eq!(my_repeat_n(7, 5).rev().count(), 5);
+        assert_eq!(my_repeat_n(7, 0).count(), 0);
+    }
+}
@@ -0,0 +1,60 @@
+use std::iter::Scan;
+use std::ops::Add;
+
+type Step<T> = fn(&mut T, T) -> Option<T>;
+
+// a scan with the accumulator fixed to the sum so far; a fn pointer keeps the type nameable
+pub struct RunningSum<I: Iterator> {
+    inner: Scan<I, I::Item, Step<I::Item>>,
+}
+
+impl<I> RunningSum<I>
+where
+    I: Iterator<Item: Copy + Add<Output = I::Item> + Default>,
+{
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            inner: iter.scan(I::Item::default(), |sum, x| {
+                *sum = *sum + x;
+                Some(*sum)
+            }),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for RunningSum<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn running_sum() {
+        let sums: Vec<_> = [1, 2, 3, 4].into_iter().my_running_sum().collect();
+        assert_eq!(sums, [1, 3, 6, 10]);
+
+        let data = [3.5, -1.0, 2.25, 0.0, 8.0];
+        let mut expected = Vec::new();
+        let mut acc = 0.0;
+        for x in data {
+            acc += x;
+            expected.push(acc);
+        }
+        assert_eq!(
+            data.into_iter().my_running_sum().collect::<Vec<_>>(),
+            expected
+        );
+
+        assert_eq!(std::iter::empty::<i32>().my_running_sum().next(), None);
+    }
+}
@@ -0,0 +1,87 @@
+// yields each item paired with the one before it, so every item except the ends is cloned once
+pub struct TupleWindows2<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+    started: bool, // the first item is only pulled by the first next(), never on construction
+}
+
+impl<I: Iterator> TupleWindows2<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self {
+            iter,
+            prev: None,
+            started: false,
+        }
+    }
+}
+
+impl<I> Iterator for TupleWindows2<I>
+where
+    I: Iterator<Item: Clone>,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            self.prev = Some(self.iter.next()?);
+        }
+        let next = self.iter.next()?;
+        let prev = self.prev.replace(next.clone())?;
+        Some((prev, next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if !self.started {
+            // the first item only opens the first window
+            let (lo, hi) = self.iter.size_hint();
+            return (lo.saturating_sub(1), hi.map(|hi| hi.saturating_sub(1)));
+        }
+        // one pair per remaining item, as long as there is a previous one
+        if self.prev.is_none() {
+            return (0, Some(0));
+        }
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn tuple_windows() {
+        let pairs: Vec<_> = [1, 2, 3].into_iter().my_tuple_windows().collect();
+        assert_eq!(pairs, [(1, 2), (2, 3)]);
+
+        let diffs: Vec<i32> = [10, 13, 9]
+            .into_iter()
+            .my_tuple_windows()
+            .map(|(a, b)| b - a)
+            .collect();
+        assert_eq!(diffs, [3, -4]);
+
+        assert_eq!([1].into_iter().my_tuple_windows().next(), None);
+        assert_eq!(std::iter::empty::<i32>().my_tuple_windows().next(), None);
+        assert_eq!(
+            [1, 2, 3, 4].iter().my_tuple_windows().size_hint(),
+            (3, Some(3))
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        let pulled = std::cell::Cell::new(0);
+        let iter = (0..3).inspect(|_| pulled.set(pulled.get() + 1));
+        let mut windows = iter.my_tuple_windows();
+        assert_eq!(pulled.get(), 0); // building it pulls nothing
+        assert_eq!(windows.size_hint(), (2, Some(2)));
+        assert_eq!(windows.next(), Some((0, 1)));
+        assert_eq!(pulled.get(), 2);
+        assert_eq!(windows.size_hint(), (1, Some(1)));
+        assert_eq!(
+            std::iter::empty::<i32>().my_tuple_windows().size_hint(),
+            (0, Some(0))
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+// keeps the current window and its total; each step adds the new item and subtracts the
+// one falling out, so the cost per item doesn't depend on n
+pub struct WindowSums<I: Iterator> {
+    iter: I,
+    n: usize,
+    window: VecDeque<I::Item>,
+    sum: Option<I::Item>, // None until the first window is full
+}
+
+impl<I: Iterator> WindowSums<I> {
+    pub(crate) fn new(iter: I, n: usize) -> Self {
+        assert!(n != 0, "window size must be non-zero");
+        Self {
+            iter,
+            n,
+            window: VecDeque::with_capacity(n),
+            sum: None,
+        }
+    }
+}
+
+impl<I> Iterator for WindowSums<I>
+where
+    I: Iterator<Item: Copy + Add<Output = I::Item> + Sub<Output = I::Item>>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sum = match self.sum {
+            Some(sum) => {
+                let item = self.iter.next()?;
+                let leaving = self.window.pop_front().unwrap();
+                self.window.push_back(item);
+                sum + item - leaving
+            }
+            None => {
+                // no zero to start from, so the first window is summed from its first item
+                while self.window.len() < self.n {
+                    self.window.push_back(self.iter.next()?);
+                }
+                self.window.iter().copied().reduce(|a, b| a + b).unwrap()
+            }
+        };
+        self.sum = Some(sum);
+        Some(sum)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        // items still needed before the first window is full
+        let missing = if self.sum.is_some() {
+            0
+        } else {
+            self.n - 1 - self.window.len().min(self.n - 1)
+        };
+        (
+            lo.saturating_sub(missing),
+            hi.map(|hi| hi.saturating_sub(missing)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IteratorExt;
+
+    #[test]
+    fn window_sums() {
+        let sums: Vec<_> = [1, 2, 3, 4].into_iter().my_window_sums(2).collect();
+        assert_eq!(sums, [3, 5, 7]);
+
+        let data = [5, -3, 8, 0, 2, 7, -1, 4];
+        for n in 1..=data.len() + 1 {
+            let naive: Vec<i32> = data.windows(n).map(|w| w.iter().sum()).collect();
+            let iter = data.into_iter().my_window_sums(n);
+            assert_eq!(iter.size_hint(), (naive.len(), Some(naive.len())));
+            assert_eq!(iter.collect::<Vec<_>>(), naive);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 pub struct Split<'a, D> {
     remainder: Option<&'a str>,
     delimiter: D,
@@ -7,7 +9,7 @@ pub trait Delimiter {
     fn find_next(&self, s: &str) -> Option<(usize, usize)>;
 }
 
-pub fn split<D: Delimiter>(s: &str, delimiter: D) -> Split<D> {
+pub fn split<D: Delimiter>(s: &str, delimiter: D) -> Split<'_, D> {
     Split {
         remainder: Some(s),
         delimiter,
@@ -19,7 +21,7 @@ impl<'a, D: Delimiter> Iterator for Split<'a, D> {
     fn next(&mut self) -> Option<Self::Item> {
         // let s = &mut self.remainder?;    // wrong
         let s = self.remainder.as_mut()?;
-        if let Some((start, end)) = self.delimiter.find_next(*s) {
+        if let Some((start, end)) = self.delimiter.find_next(s) {
             let ret = &s[..start];
             *s = &s[end..];
             Some(ret)
@@ -29,6 +31,41 @@ impl<'a, D: Delimiter> Iterator for Split<'a, D> {
     }
 }
 
+pub struct Matches<'a, D> {
+    remainder: Option<&'a str>,
+    delimiter: D,
+}
+
+pub fn matches<D: Delimiter>(s: &str, delimiter: D) -> Matches<'_, D> {
+    Matches {
+        remainder: Some(s),
+        delimiter,
+    }
+}
+
+pub fn count_delimiters<D: Delimiter>(s: &str, delimiter: D) -> usize {
+    matches(s, delimiter).count()
+}
+
+impl<'a, D: Delimiter> Iterator for Matches<'a, D> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.remainder?;
+        let Some((start, end)) = self.delimiter.find_next(s) else {
+            self.remainder = None;
+            return None;
+        };
+        let ret = &s[start..end];
+        if end > start {
+            self.remainder = Some(&s[end..]);
+        } else {
+            // zero-width match: step over one char so we always make progress
+            self.remainder = s[end..].chars().next().map(|c| &s[end + c.len_utf8()..]);
+        }
+        Some(ret)
+    }
+}
+
 impl Delimiter for char {
     fn find_next(&self, s: &str) -> Option<(usize, usize)> {
         s.char_indices()
@@ -49,6 +86,63 @@ impl<F: Fn(char) -> bool> Delimiter for F {
     }
 }
 
+/// Delimiter defined by a closure that, given the rest of the string, returns the length in
+/// bytes of a delimiter anchored at its start. A wrapper is needed because a second blanket
+/// impl over `F` would overlap with the `Fn(char) -> bool` one.
+pub struct Prefix<F>(pub F);
+
+impl<F: Fn(&str) -> Option<usize>> Delimiter for Prefix<F> {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        s.char_indices()
+            .find_map(|(idx, _)| match (self.0)(&s[idx..]) {
+                Some(len) if len > 0 => Some((idx, idx + len)), // zero-width matches are ignored
+                _ => None,
+            })
+    }
+}
+
+/// Stops matching after `max` delimiters, so `split` leaves the rest as one last piece:
+/// `split(s, Limited::new(',', 2))` behaves like `s.splitn(3, ',')`.
+///
+/// The count lives in a `Cell` because `find_next` only gets `&self`; a `Limited` is used up
+/// once it has matched `max` times, so build a fresh one per `split`.
+pub struct Limited<D> {
+    inner: D,
+    remaining: Cell<usize>,
+}
+
+impl<D> Limited<D> {
+    pub fn new(inner: D, max: usize) -> Self {
+        Self {
+            inner,
+            remaining: Cell::new(max),
+        }
+    }
+}
+
+impl<D: Delimiter> Delimiter for Limited<D> {
+    fn find_next(&self, s: &str) -> Option<(usize, usize)> {
+        let remaining = self.remaining.get();
+        if remaining == 0 {
+            return None;
+        }
+        let found = self.inner.find_next(s)?;
+        self.remaining.set(remaining - 1);
+        Some(found)
+    }
+}
+
+fn whitespace_run(s: &str) -> Option<usize> {
+    let len = s.len() - s.trim_start().len();
+    (len > 0).then_some(len)
+}
+
+/// Splits on runs of Unicode whitespace like `str::split_whitespace`. Each run is matched as
+/// one delimiter, so only leading and trailing whitespace can leave empty pieces to skip.
+pub fn split_whitespace(s: &str) -> impl Iterator<Item = &str> {
+    split(s, Prefix(whitespace_run)).filter(|piece| !piece.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,4 +170,55 @@ mod tests {
         let res: Vec<_> = split(s, |c: char| c.is_numeric()).collect();
         assert_eq!(expect, res);
     }
+
+    #[test]
+    fn matches_char() {
+        let res: Vec<_> = matches("a-b-c", '-').collect();
+        assert_eq!(res, vec!["-", "-"]);
+        assert_eq!(count_delimiters("a-b-c", '-'), 2);
+        assert_eq!(count_delimiters("abc", '-'), 0);
+    }
+
+    #[test]
+    fn matches_str() {
+        let s = "apple>>banana>>cherry>>";
+        let expect: Vec<_> = s.matches(">>").collect();
+        let res: Vec<_> = matches(s, ">>").collect();
+        assert_eq!(expect, res);
+    }
+
+    #[test]
+    fn matches_zero_width() {
+        assert_eq!(count_delimiters("abc", ""), "abc".matches("").count());
+    }
+
+    #[test]
+    fn it_works_prefix() {
+        let whitespace_run = Prefix(|s: &str| {
+            let len = s.len() - s.trim_start().len();
+            (len > 0).then_some(len)
+        });
+        let res: Vec<_> = split("a   b\tc", whitespace_run).collect();
+        assert_eq!(res, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn it_works_split_whitespace() {
+        for s in ["  a  b  c  ", "a\u{3000}b\n\tc", "", "   ", "single"] {
+            let expect: Vec<_> = s.split_whitespace().collect();
+            let res: Vec<_> = split_whitespace(s).collect();
+            assert_eq!(expect, res);
+        }
+    }
+
+    #[test]
+    fn it_works_limited() {
+        for s in ["a,b,c,d", "a,b", "", "a,b,c"] {
+            let expect: Vec<_> = s.splitn(3, ',').collect();
+            let res: Vec<_> = split(s, Limited::new(',', 2)).collect();
+            assert_eq!(expect, res);
+        }
+        let res: Vec<_> = split("a>>b>>c", Limited::new(">>", 0)).collect();
+        assert_eq!(res, vec!["a>>b>>c"]);
+    }
 }
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct SpinBarrier {
+    n: usize,
+    count: AtomicUsize,
+    generation: AtomicUsize, // bumped each time the barrier releases, so it can be reused
+}
+
+impl SpinBarrier {
+    pub const fn new(n: usize) -> Self {
+        Self {
+            n,
+            count: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    /// Spins until `n` threads have called `wait`. Returns true for exactly one thread per
+    /// phase, the last one to arrive.
+    pub fn wait(&self) -> bool {
+        let generation = self.generation.load(Ordering::Acquire);
+        if self.count.fetch_add(1, Ordering::AcqRel) + 1 >= self.n {
+            // reset before releasing, so a fast thread re-entering counts towards the next phase
+            self.count.store(0, Ordering::Relaxed);
+            self.generation.fetch_add(1, Ordering::Release);
+            return true;
+        }
+        while self.generation.load(Ordering::Acquire) == generation {
+            std::hint::spin_loop();
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn it_works() {
+        const N: usize = 4;
+        const PHASES: usize = 50;
+
+        let barrier = Arc::new(SpinBarrier::new(N));
+        let arrivals: Arc<Vec<AtomicUsize>> =
+            Arc::new((0..PHASES).map(|_| AtomicUsize::new(0)).collect());
+        let leaders = Arc::new(AtomicUsize::new(0));
+
+        let threads: Vec<_> = (0..N)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                let arrivals = Arc::clone(&arrivals);
+                let leaders = Arc::clone(&leaders);
+                thread::spawn(move || {
+                    for arrived in arrivals.iter() {
+                        arrived.fetch_add(1, Ordering::Relaxed);
+                        if barrier.wait() {
+                            leaders.fetch_add(1, Ordering::Relaxed);
+                        }
+                        // nobody gets past the barrier until everyone has arrived
+                        assert_eq!(arrived.load(Ordering::Relaxed), N);
+                    }
+                })
+            })
+            .collect();
+
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().expect("failed"));
+        assert_eq!(leaders.load(Ordering::Relaxed), PHASES);
+    }
+}
@@ -0,0 +1,229 @@
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering, fence};
+use std::thread::{self, Thread};
+
+use crate::{SpinLock, SpinLockGuard};
+
+const SPIN_LIMIT: usize = 100;
+
+/// A `SpinLock` whose waiters, after a bounded number of failed spins, park in a FIFO queue
+/// and are woken one at a time on unlock, so they neither burn CPU nor starve indefinitely.
+///
+/// The queue lives here rather than in `SpinLock` so plain locks don't pay for it: their
+/// unlock is a single Release store, while this one adds a SeqCst fence and a look at the
+/// queue, see `FairSpinLockGuard`'s `Drop`.
+pub struct FairSpinLock<T> {
+    lock: SpinLock<T>,
+    waiters: WaitQueue,
+}
+
+// WaitQueue is only touched under its own lock; the rest is as Sync as SpinLock<T>
+unsafe impl<T: Send> Send for FairSpinLock<T> {}
+unsafe impl<T: Send> Sync for FairSpinLock<T> {}
+
+impl<T> FairSpinLock<T> {
+    pub const fn new(data: T) -> Self {
+        Self {
+            lock: SpinLock::new(data),
+            waiters: WaitQueue::new(),
+        }
+    }
+
+    pub fn lock(&self) -> FairSpinLockGuard<'_, T> {
+        self.lock.check_reentrant();
+        for _spins in 0..SPIN_LIMIT {
+            if let Some(guard) = self.lock.try_lock() {
+                #[cfg(feature = "metrics")]
+                self.lock.record(_spins as u64);
+                return self.wrap(guard);
+            }
+            std::hint::spin_loop();
+        }
+        #[cfg(feature = "metrics")]
+        self.lock.record(SPIN_LIMIT as u64); // parking time isn't spinning
+
+        let me = thread::current();
+        let mut woken = false;
+        loop {
+            // a thread woken by unlock goes back to the front so it keeps its place
+            let acquired = self.waiters.with(|queue| {
+                if woken {
+                    queue.push_front(me.clone());
+                } else {
+                    queue.push_back(me.clone());
+                }
+                self.waiters.parked.store(queue.len(), Ordering::Relaxed);
+                // store `parked`, fence, then read `locked`; the unlock does the mirror image,
+                // so the two SeqCst fences guarantee that either this try_lock sees the lock
+                // free or the unlock sees us parked. Acquire/Release alone can't rule out both
+                // missing each other.
+                fence(Ordering::SeqCst);
+                let guard = self.lock.try_lock()?;
+                remove_thread(queue, &me);
+                self.waiters.parked.store(queue.len(), Ordering::Relaxed);
+                Some(guard)
+            });
+            if let Some(guard) = acquired {
+                return self.wrap(guard);
+            }
+
+            thread::park();
+            woken = self.waiters.with(|queue| {
+                // still queued means the wakeup was spurious
+                let spurious = remove_thread(queue, &me);
+                self.waiters.parked.store(queue.len(), Ordering::Relaxed);
+                !spurious
+            });
+            if let Some(guard) = self.lock.try_lock() {
+                return self.wrap(guard);
+            }
+        }
+    }
+
+    /// Takes the lock if it is free, without spinning or queueing.
+    pub fn try_lock(&self) -> Option<FairSpinLockGuard<'_, T>> {
+        self.lock.try_lock().map(|guard| self.wrap(guard))
+    }
+
+    /// See `SpinLock::stats`; parked time isn't counted as spinning.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> (u64, u64) {
+        self.lock.stats()
+    }
+
+    fn wrap<'a>(&'a self, guard: SpinLockGuard<'a, T>) -> FairSpinLockGuard<'a, T> {
+        FairSpinLockGuard {
+            guard: ManuallyDrop::new(guard),
+            waiters: &self.waiters,
+        }
+    }
+}
+
+pub struct FairSpinLockGuard<'a, T: 'a> {
+    guard: ManuallyDrop<SpinLockGuard<'a, T>>,
+    waiters: &'a WaitQueue,
+}
+
+impl<T> Deref for FairSpinLockGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for FairSpinLockGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for FairSpinLockGuard<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: dropped exactly once, here
+        unsafe { ManuallyDrop::drop(&mut self.guard) }; // the usual Release unlock
+        // pairs with the fence in FairSpinLock::lock
+        fence(Ordering::SeqCst);
+        if self.waiters.parked.load(Ordering::Relaxed) > 0 {
+            let next = self.waiters.with(|queue| {
+                let next = queue.pop_front();
+                self.waiters.parked.store(queue.len(), Ordering::Relaxed);
+                next
+            });
+            if let Some(thread) = next {
+                thread.unpark();
+            }
+        }
+    }
+}
+
+fn remove_thread(queue: &mut VecDeque<Thread>, thread: &Thread) -> bool {
+    match queue.iter().position(|t| t.id() == thread.id()) {
+        Some(pos) => {
+            queue.remove(pos);
+            true
+        }
+        None => false,
+    }
+}
+
+// FIFO of parked threads, guarded by its own tiny spinlock
+struct WaitQueue {
+    locked: AtomicBool,
+    parked: AtomicUsize, // mirrors threads.len() so an unlock can skip the queue when empty
+    threads: UnsafeCell<VecDeque<Thread>>,
+}
+
+impl WaitQueue {
+    const fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            parked: AtomicUsize::new(0),
+            threads: UnsafeCell::new(VecDeque::new()),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut VecDeque<Thread>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            std::hint::spin_loop();
+        }
+        // SAFETY: `locked` gives us exclusive access to the queue
+        let ret = f(unsafe { &mut *self.threads.get() });
+        self.locked.store(false, Ordering::Release);
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn bounded_latency() {
+        use std::time::{Duration, Instant};
+
+        const N: usize = 8;
+        const ITERS: usize = 200;
+
+        let lock = Arc::new(FairSpinLock::new(0));
+        let threads: Vec<_> = (0..N)
+            .map(|_| {
+                let lock = Arc::clone(&lock);
+                thread::spawn(move || {
+                    let mut max_wait = Duration::ZERO;
+                    for _ in 0..ITERS {
+                        let start = Instant::now();
+                        let mut guard = lock.lock();
+                        max_wait = max_wait.max(start.elapsed());
+                        *guard += 1;
+                        thread::sleep(Duration::from_micros(10));
+                    }
+                    max_wait
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            let max_wait = thread.join().expect("failed");
+            assert!(max_wait < Duration::from_secs(1), "waited {max_wait:?}");
+        }
+        assert_eq!(*lock.lock(), N * ITERS);
+    }
+
+    #[test]
+    fn try_lock() {
+        let lock = FairSpinLock::new(1);
+        let guard = lock.try_lock().unwrap();
+        assert!(lock.try_lock().is_none());
+        drop(guard);
+        *lock.lock() += 1;
+        assert_eq!(*lock.try_lock().unwrap(), 2);
+    }
+}
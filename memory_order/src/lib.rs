@@ -1,57 +1,290 @@
 use std::cell::UnsafeCell;
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-pub struct SpinLock<T> {
+pub mod barrier;
+pub mod fair;
+pub mod spin_arc;
+pub mod spin_queue;
+pub mod strategy;
+
+use strategy::{DefaultSpin, SpinStrategy};
+
+/// `S` decides how `lock` waits while the lock is taken, see `strategy`.
+pub struct SpinLock<T, S = DefaultSpin> {
     locked: AtomicBool,
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
+    #[cfg(debug_assertions)]
+    owner: std::sync::atomic::AtomicU64, // holder's thread_id(), 0 = unlocked
     data: UnsafeCell<T>,
+    strategy: PhantomData<fn() -> S>, // a fresh S is made per lock call, none is stored
+}
+
+// contention counters, only compiled in with the `metrics` feature
+#[cfg(feature = "metrics")]
+struct Metrics {
+    locks: std::sync::atomic::AtomicU64,
+    spins: std::sync::atomic::AtomicU64,
 }
 
-unsafe impl<T: Send> Send for SpinLock<T> {}
-unsafe impl<T: Send> Sync for SpinLock<T> {}
+unsafe impl<T: Send, S> Send for SpinLock<T, S> {}
+unsafe impl<T: Send, S> Sync for SpinLock<T, S> {}
 
 impl<T> SpinLock<T> {
     pub const fn new(data: T) -> Self {
+        Self::with_strategy(data)
+    }
+}
+
+impl<T, S: SpinStrategy> SpinLock<T, S> {
+    /// Like `new`, for a lock that waits with `S`, e.g.
+    /// `SpinLock::<_, YieldSpin>::with_strategy(data)`.
+    pub const fn with_strategy(data: T) -> Self {
         Self {
             locked: AtomicBool::new(false),
+            #[cfg(feature = "metrics")]
+            metrics: Metrics {
+                locks: std::sync::atomic::AtomicU64::new(0),
+                spins: std::sync::atomic::AtomicU64::new(0),
+            },
+            #[cfg(debug_assertions)]
+            owner: std::sync::atomic::AtomicU64::new(0),
             data: UnsafeCell::new(data),
+            strategy: PhantomData,
         }
     }
 
-    pub fn lock(&self) -> SpinLockGuard<'_, T> {
+    pub fn lock(&self) -> SpinLockGuard<'_, T, S> {
+        self.check_reentrant();
+        let mut strategy = S::default();
+        let mut spins = 0;
         while self
             .locked
             .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
             .is_err()
         {
-            while self.locked.load(Ordering::Relaxed) {} // avoid false sharing
+            // spin on a plain load to avoid false sharing
+            while self.locked.load(Ordering::Relaxed) {
+                strategy.spin(spins);
+                spins += 1;
+            }
+        }
+        #[cfg(feature = "metrics")]
+        self.record(spins as u64);
+        self.guard()
+    }
+
+    /// `(lock calls, spin iterations)` so far, including locks taken through a `FairSpinLock`.
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.metrics.locks.load(Ordering::Relaxed),
+            self.metrics.spins.load(Ordering::Relaxed),
+        )
+    }
+
+    // one pair of relaxed adds per acquisition, after the fact, to keep the spin loop clean
+    #[cfg(feature = "metrics")]
+    fn record(&self, spins: u64) {
+        self.metrics.locks.fetch_add(1, Ordering::Relaxed);
+        self.metrics.spins.fetch_add(spins, Ordering::Relaxed);
+    }
+
+    /// Takes the lock if it is free, without spinning.
+    pub fn try_lock(&self) -> Option<SpinLockGuard<'_, T, S>> {
+        if self.try_acquire() {
+            Some(self.guard())
+        } else {
+            None
         }
-        return SpinLockGuard { lock: self };
     }
 
+    /// Consumes the guard without unlocking and returns a reference that lives as long
+    /// as the lock, like `MutexGuard::leak`.
+    ///
+    /// The lock stays held forever: every later `lock` deadlocks and
+    /// `try_lock` always returns `None`. Only use this for data that is never shared again,
+    /// such as a one-time global init.
+    pub fn leak<'a>(guard: SpinLockGuard<'a, T, S>) -> &'a mut T {
+        let lock = guard.lock;
+        std::mem::forget(guard);
+        // SAFETY: the lock is never released, so this is the only access from now on
+        unsafe { &mut *lock.data.get() }
+    }
+
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        // the guard is dropped as soon as f returns, so it can't outlive the call
+        let mut guard = self.lock();
+        f(&mut guard)
+    }
+
+    // every acquisition goes through here, so debug builds know who holds the lock
+    fn guard(&self) -> SpinLockGuard<'_, T, S> {
+        #[cfg(debug_assertions)]
+        self.owner.store(thread_id(), Ordering::Relaxed);
+        SpinLockGuard { lock: self }
+    }
+
+    // spinning on a lock we hold ourselves would hang forever; fail loudly instead
+    fn check_reentrant(&self) {
+        #[cfg(debug_assertions)]
+        if self.owner.load(Ordering::Relaxed) == thread_id() {
+            panic!("re-entrant spinlock deadlock");
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+// needs no strategy, so guards can release the lock whatever S is
+impl<T, S> SpinLock<T, S> {
     fn unlock(&self) {
+        #[cfg(debug_assertions)]
+        self.owner.store(0, Ordering::Relaxed);
         self.locked.store(false, Ordering::Release);
     }
 }
 
-pub struct SpinLockGuard<'a, T: 'a> {
-    lock: &'a SpinLock<T>,
+// a small non-zero id per thread; ThreadId::as_u64 is still unstable
+#[cfg(debug_assertions)]
+fn thread_id() -> u64 {
+    use std::sync::atomic::AtomicU64;
+
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static ID: u64 = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    ID.with(|id| *id)
+}
+
+pub struct SpinLockGuard<'a, T: 'a, S = DefaultSpin> {
+    lock: &'a SpinLock<T, S>,
 }
 
-impl<T> Deref for SpinLockGuard<'_, T> {
+impl<T, S> Deref for SpinLockGuard<'_, T, S> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         unsafe { &*self.lock.data.get() }
     }
 }
 
-impl<T> DerefMut for SpinLockGuard<'_, T> {
+impl<T, S> DerefMut for SpinLockGuard<'_, T, S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe { &mut *self.lock.data.get() }
     }
 }
 
-impl<T> Drop for SpinLockGuard<'_, T> {
+impl<T, S> Drop for SpinLockGuard<'_, T, S> {
+    fn drop(&mut self) {
+        self.lock.unlock();
+    }
+}
+
+// lets a mapped guard release the lock without naming the original T
+trait Unlock {
+    fn unlock(&self);
+}
+
+impl<T, S> Unlock for SpinLock<T, S> {
+    fn unlock(&self) {
+        SpinLock::unlock(self)
+    }
+}
+
+impl<'a, T: 'a, S: SpinStrategy> SpinLockGuard<'a, T, S> {
+    /// Narrows the guard to a part of the data, keeping the lock held.
+    pub fn map<U: ?Sized, F>(orig: Self, f: F) -> MappedSpinLockGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        match Self::try_map(orig, |data| Some(f(data))) {
+            Ok(mapped) => mapped,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Like `map`, but the projection may fail, in which case the original guard is
+    /// handed back still locked, e.g. to look into an enum variant that isn't active.
+    pub fn try_map<U: ?Sized, F>(orig: Self, f: F) -> Result<MappedSpinLockGuard<'a, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        // SAFETY: we hold the lock, and the reference doesn't outlive the mapped guard
+        match f(unsafe { &mut *orig.lock.data.get() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let lock = orig.lock;
+                std::mem::forget(orig); // the mapped guard takes over the unlock
+                Ok(MappedSpinLockGuard {
+                    lock,
+                    data,
+                    _marker: PhantomData,
+                })
+            }
+            None => Err(orig),
+        }
+    }
+}
+
+pub struct MappedSpinLockGuard<'a, U: ?Sized + 'a> {
+    lock: &'a dyn Unlock,
+    data: NonNull<U>,
+    _marker: PhantomData<&'a mut U>, // invariance need, see tests/ui/mapped_guard_variance.rs
+}
+
+impl<'a, U: ?Sized + 'a> MappedSpinLockGuard<'a, U> {
+    pub fn map<V: ?Sized, F>(orig: Self, f: F) -> MappedSpinLockGuard<'a, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        match Self::try_map(orig, |data| Some(f(data))) {
+            Ok(mapped) => mapped,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    pub fn try_map<V: ?Sized, F>(mut orig: Self, f: F) -> Result<MappedSpinLockGuard<'a, V>, Self>
+    where
+        F: FnOnce(&mut U) -> Option<&mut V>,
+    {
+        // SAFETY: same as SpinLockGuard::try_map, the lock is still held by orig
+        match f(unsafe { orig.data.as_mut() }) {
+            Some(data) => {
+                let data = NonNull::from(data);
+                let lock = orig.lock;
+                std::mem::forget(orig);
+                Ok(MappedSpinLockGuard {
+                    lock,
+                    data,
+                    _marker: PhantomData,
+                })
+            }
+            None => Err(orig),
+        }
+    }
+}
+
+impl<U: ?Sized> Deref for MappedSpinLockGuard<'_, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<U: ?Sized> DerefMut for MappedSpinLockGuard<'_, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<U: ?Sized> Drop for MappedSpinLockGuard<'_, U> {
     fn drop(&mut self) {
         self.lock.unlock();
     }
@@ -99,4 +332,145 @@ mod tests {
 
         assert_eq!(*res_spinlock.lock(), 800);
     }
+
+    #[test]
+    fn with_counter() {
+        const N: usize = 8;
+        const ITERS: usize = 1000;
+
+        let counter = Arc::new(SpinLock::new(0));
+        let threads: Vec<_> = (0..N)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        counter.with(|c| *c += 1);
+                    }
+                })
+            })
+            .collect();
+
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().expect("failed"));
+
+        assert_eq!(counter.with(|c| *c), N * ITERS);
+    }
+
+    #[test]
+    fn leak() {
+        static LOCK: SpinLock<Vec<i32>> = SpinLock::new(Vec::new());
+
+        let data: &'static mut Vec<i32> = SpinLock::leak(LOCK.lock());
+        data.push(1);
+        data.push(2);
+        assert_eq!(*data, [1, 2]);
+        assert!(LOCK.try_lock().is_none());
+        assert!(LOCK.try_lock().is_none());
+    }
+
+    #[test]
+    fn try_lock() {
+        let lock = SpinLock::new(1);
+        let guard = lock.try_lock().unwrap();
+        assert!(lock.try_lock().is_none());
+        drop(guard);
+        *lock.try_lock().unwrap() += 1;
+        assert_eq!(*lock.lock(), 2);
+    }
+
+    #[test]
+    fn try_map() {
+        let lock = SpinLock::new(Some(1));
+
+        let mut inner = SpinLockGuard::try_map(lock.lock(), |o| o.as_mut())
+            .ok()
+            .unwrap();
+        *inner += 1;
+        assert!(lock.try_lock().is_none());
+        drop(inner);
+        assert_eq!(*lock.lock(), Some(2));
+
+        *lock.lock() = None;
+        let guard = match SpinLockGuard::try_map(lock.lock(), |o| o.as_mut()) {
+            Ok(_) => panic!("projected into None"),
+            Err(guard) => guard,
+        };
+        assert!(guard.is_none());
+        assert!(lock.try_lock().is_none()); // still locked by the returned guard
+        drop(guard);
+
+        let lock = SpinLock::new((1, vec![2, 3]));
+        let mut tail = SpinLockGuard::map(lock.lock(), |(_, v)| v.as_mut_slice());
+        tail[0] = 20;
+        let last = MappedSpinLockGuard::try_map(tail, |v| v.last_mut())
+            .ok()
+            .unwrap();
+        assert_eq!(*last, 3);
+        drop(last);
+        assert_eq!(*lock.lock(), (1, vec![20, 3]));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn stats() {
+        const N: usize = 4;
+        const ITERS: usize = 1000;
+
+        let lock = Arc::new(SpinLock::new(0));
+        let threads: Vec<_> = (0..N)
+            .map(|_| {
+                let lock = Arc::clone(&lock);
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        let mut guard = lock.lock();
+                        // hold the lock a little so the others have to spin
+                        for _ in 0..100 {
+                            std::hint::spin_loop();
+                        }
+                        *guard += 1;
+                    }
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().expect("failed"));
+
+        let (locks, spins) = lock.stats();
+        assert_eq!(locks, (N * ITERS) as u64);
+        assert!(spins > locks, "{spins} spins for {locks} locks");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn reentrant_lock_panics() {
+        use std::time::{Duration, Instant};
+
+        let lock = Arc::new(SpinLock::new(0));
+        let handle = {
+            let lock = Arc::clone(&lock);
+            thread::spawn(move || {
+                let _a = lock.lock();
+                let _b = lock.lock();
+            })
+        };
+        // watchdog: a hang here means the detector missed it
+        let start = Instant::now();
+        while !handle.is_finished() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "re-entrant lock hung"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+        let err = handle.join().unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<&str>(),
+            Some(&"re-entrant spinlock deadlock")
+        );
+
+        // the panic unwound through the first guard, so the lock is free again
+        assert_eq!(*lock.try_lock().unwrap(), 0);
+    }
 }
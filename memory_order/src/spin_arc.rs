@@ -0,0 +1,117 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering, fence};
+
+use crate::{SpinLock, SpinLockGuard};
+
+/// Roughly `Arc<SpinLock<T>>`, but the count and the lock share one allocation, so reaching
+/// the data takes one pointer hop instead of two.
+pub struct SpinArc<T> {
+    ptr: NonNull<SpinArcInner<T>>,
+    phantom: PhantomData<SpinArcInner<T>>, // drop check
+}
+
+struct SpinArcInner<T> {
+    count: AtomicUsize,
+    lock: SpinLock<T>,
+}
+
+// same bounds as Arc<SpinLock<T>>: the SpinLock makes the data Sync as long as it's Send
+unsafe impl<T: Send> Send for SpinArc<T> {}
+unsafe impl<T: Send> Sync for SpinArc<T> {}
+
+impl<T> SpinArc<T> {
+    pub fn new(data: T) -> Self {
+        let inner = Box::new(SpinArcInner {
+            count: AtomicUsize::new(1),
+            lock: SpinLock::new(data),
+        });
+        Self {
+            ptr: NonNull::from(Box::leak(inner)),
+            phantom: PhantomData,
+        }
+    }
+
+    fn inner(&self) -> &SpinArcInner<T> {
+        // SAFETY: the allocation lives as long as any SpinArc points to it
+        unsafe { self.ptr.as_ref() }
+    }
+
+    pub fn lock(&self) -> SpinLockGuard<'_, T> {
+        self.inner().lock.lock()
+    }
+
+    pub fn count(this: &Self) -> usize {
+        this.inner().count.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Clone for SpinArc<T> {
+    fn clone(&self) -> Self {
+        // Relaxed is enough: the new handle comes from an existing one, which keeps it alive
+        let old = self.inner().count.fetch_add(1, Ordering::Relaxed);
+        if old > isize::MAX as usize {
+            std::process::abort(); // leaked clones overflowed the count
+        }
+        Self {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for SpinArc<T> {
+    fn drop(&mut self) {
+        // Release publishes our last use of the data; the final owner's Acquire fence pairs
+        // with every other owner's release before it frees the allocation
+        if self.inner().count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        fence(Ordering::Acquire);
+        drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn shared_push() {
+        const N: usize = 8;
+        const ITERS: usize = 1000;
+
+        let data = SpinArc::new(Vec::new());
+        let threads: Vec<_> = (0..N)
+            .map(|t| {
+                let data = data.clone();
+                thread::spawn(move || {
+                    for i in 0..ITERS {
+                        data.lock().push((t * ITERS + i) as i32);
+                    }
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().expect("failed"));
+
+        assert_eq!(SpinArc::count(&data), 1);
+        let mut data = data.lock();
+        assert_eq!(data.len(), N * ITERS);
+        data.sort();
+        assert!(data.iter().enumerate().all(|(i, &x)| x == i as i32));
+    }
+
+    #[test]
+    fn drops_once() {
+        let value = std::sync::Arc::new(());
+        let a = SpinArc::new(std::sync::Arc::clone(&value));
+        let b = a.clone();
+        drop(a);
+        assert_eq!(std::sync::Arc::strong_count(&value), 2);
+        drop(b);
+        assert_eq!(std::sync::Arc::strong_count(&value), 1);
+    }
+}
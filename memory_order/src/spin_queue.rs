@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+
+use crate::SpinLock;
+
+/// A `VecDeque` behind a `SpinLock`, for short cross-thread hand-offs where a condvar
+/// would be overkill. Nothing ever blocks in the kernel: `pop_spin` busy-waits instead.
+pub struct SpinQueue<T> {
+    queue: SpinLock<VecDeque<T>>,
+    capacity: usize, // only enforced by push_bounded
+}
+
+impl<T> SpinQueue<T> {
+    pub const fn new() -> Self {
+        Self::with_capacity(usize::MAX)
+    }
+
+    pub const fn with_capacity(capacity: usize) -> Self {
+        Self {
+            queue: SpinLock::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, t: T) {
+        self.queue.with(|queue| queue.push_back(t));
+    }
+
+    /// Like `push`, but hands `t` back if the queue already holds `capacity` items.
+    pub fn push_bounded(&self, t: T) -> Result<(), T> {
+        self.queue.with(|queue| {
+            if queue.len() >= self.capacity {
+                return Err(t);
+            }
+            queue.push_back(t);
+            Ok(())
+        })
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        self.queue.with(|queue| queue.pop_front())
+    }
+
+    /// Spins until an item is available.
+    pub fn pop_spin(&self) -> T {
+        loop {
+            if let Some(t) = self.pop() {
+                return t;
+            }
+            std::hint::spin_loop();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.with(|queue| queue.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for SpinQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn bounded() {
+        let q = SpinQueue::with_capacity(2);
+        assert_eq!(q.push_bounded(1), Ok(()));
+        assert_eq!(q.push_bounded(2), Ok(()));
+        assert_eq!(q.push_bounded(3), Err(3));
+        assert_eq!(q.pop(), Some(1));
+        assert_eq!(q.push_bounded(3), Ok(()));
+        assert_eq!(q.len(), 2);
+    }
+
+    #[test]
+    fn stress() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const ITEMS: usize = 10_000;
+
+        let q = Arc::new(SpinQueue::new());
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let q = Arc::clone(&q);
+                thread::spawn(move || {
+                    for i in 0..ITEMS {
+                        q.push(p * ITEMS + i);
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let q = Arc::clone(&q);
+                thread::spawn(move || {
+                    (0..PRODUCERS * ITEMS / CONSUMERS)
+                        .map(|_| q.pop_spin())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        producers
+            .into_iter()
+            .for_each(|thread| thread.join().expect("failed"));
+        let mut seen: Vec<_> = consumers
+            .into_iter()
+            .flat_map(|thread| thread.join().expect("failed"))
+            .collect();
+        seen.sort();
+        assert_eq!(seen, (0..PRODUCERS * ITEMS).collect::<Vec<_>>());
+        assert!(q.is_empty());
+    }
+}
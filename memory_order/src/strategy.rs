@@ -0,0 +1,119 @@
+use std::thread;
+use std::time::Duration;
+
+const YIELD_THRESHOLD: usize = 64;
+const MAX_SLEEP_SHIFT: usize = 10; // caps the backoff at 2^10 us, about 1ms
+
+/// How `SpinLock::lock` waits between attempts while the lock is held by someone else.
+///
+/// A fresh strategy is made with `Default` for every `lock` call, and `spin` is called once
+/// per failed check with the number of previous failures, starting at 0.
+pub trait SpinStrategy: Default {
+    fn spin(&mut self, iteration: usize);
+}
+
+/// Pure busy-waiting with `hint::spin_loop`. Lowest latency, burns a core while waiting.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultSpin;
+
+impl SpinStrategy for DefaultSpin {
+    fn spin(&mut self, _iteration: usize) {
+        std::hint::spin_loop();
+    }
+}
+
+/// Busy-waits for a while, then yields the time slice on every further iteration, so a
+/// lock holder that got preempted can run again.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct YieldSpin;
+
+impl SpinStrategy for YieldSpin {
+    fn spin(&mut self, iteration: usize) {
+        if iteration < YIELD_THRESHOLD {
+            std::hint::spin_loop();
+        } else {
+            thread::yield_now();
+        }
+    }
+}
+
+/// Sleeps for an exponentially growing time, from 1us up to about 1ms. Cheap on CPU for
+/// long holds, but a waiter may oversleep the unlock by up to its current backoff.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SleepSpin;
+
+impl SpinStrategy for SleepSpin {
+    fn spin(&mut self, iteration: usize) {
+        thread::sleep(Duration::from_micros(1 << iteration.min(MAX_SLEEP_SHIFT)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpinLock;
+    use std::sync::Arc;
+
+    fn contended_counter<S: SpinStrategy + 'static>() {
+        const N: usize = 4;
+        const ITERS: usize = 200;
+
+        let counter = Arc::new(SpinLock::<usize, S>::with_strategy(0));
+        let threads: Vec<_> = (0..N)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        *counter.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+        threads.into_iter().for_each(|t| t.join().unwrap());
+        assert_eq!(*counter.lock(), N * ITERS);
+    }
+
+    #[test]
+    fn default_spin() {
+        contended_counter::<DefaultSpin>();
+    }
+
+    #[test]
+    fn yield_spin() {
+        contended_counter::<YieldSpin>();
+    }
+
+    #[test]
+    fn sleep_spin() {
+        contended_counter::<SleepSpin>();
+    }
+
+    #[test]
+    fn strategy_sees_iterations() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static LAST: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Default)]
+        struct Record;
+
+        impl SpinStrategy for Record {
+            fn spin(&mut self, iteration: usize) {
+                LAST.store(iteration, Ordering::Relaxed);
+                thread::yield_now();
+            }
+        }
+
+        let lock = Arc::new(SpinLock::<_, Record>::with_strategy(()));
+        let guard = lock.lock();
+        let waiter = {
+            let lock = Arc::clone(&lock);
+            thread::spawn(move || drop(lock.lock()))
+        };
+        while LAST.load(Ordering::Relaxed) < 10 {
+            thread::yield_now();
+        }
+        drop(guard);
+        waiter.join().unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+use memory_order::{MappedSpinLockGuard, SpinLock, SpinLockGuard};
+
+// a mapped guard must be invariant, or a short-lived &str could be written into a
+// SpinLock<&'static str> through it
+fn shorten<'a, 'b>(g: MappedSpinLockGuard<'a, &'static str>) -> MappedSpinLockGuard<'a, &'b str> {
+    g
+}
+
+fn main() {
+    let lock = SpinLock::new(("", 0));
+    let guard = SpinLockGuard::map(lock.lock(), |(s, _)| s);
+    let local = String::from("gone soon");
+    *shorten(guard) = &local;
+}
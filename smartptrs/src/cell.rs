@@ -22,6 +22,24 @@ impl<T> Cell<T> {
             *self.v.get() = val;
         }
     }
+
+    pub fn replace(&self, val: T) -> T {
+        // SAFETY: !Sync, and no reference into the cell outlives this call
+        unsafe { std::mem::replace(&mut *self.v.get(), val) }
+    }
+}
+
+impl<T: Default> Cell<T> {
+    // moves the value out without cloning; Option<T> takes as None, Vec/String as empty
+    pub fn take(&self) -> T {
+        self.replace(T::default())
+    }
+}
+
+impl<T: Default> Default for Cell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
 }
 
 impl<T: Copy> Cell<T> {
@@ -29,6 +47,44 @@ impl<T: Copy> Cell<T> {
         // SAFETY:
         unsafe { *self.v.get() }
     }
+
+    // the new value is only stored if f succeeds, otherwise the cell keeps its old value
+    pub fn try_update<E, F: FnOnce(T) -> Result<T, E>>(&self, f: F) -> Result<(), E> {
+        self.set(f(self.get())?);
+        Ok(())
+    }
+}
+
+impl<T: Copy + PartialEq> Cell<T> {
+    // shaped like an atomic CAS so the same code ports to atomics; !Sync means no race here
+    pub fn compare_and_set(&self, expected: T, new: T) -> bool {
+        if self.get() != expected {
+            return false;
+        }
+        self.set(new);
+        true
+    }
+}
+
+// comparisons go by value, like std's Cell
+impl<T: PartialEq + Copy> PartialEq for Cell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: Eq + Copy> Eq for Cell<T> {}
+
+impl<T: PartialOrd + Copy> PartialOrd for Cell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.get().partial_cmp(&other.get())
+    }
+}
+
+impl<T: Ord + Copy> Ord for Cell<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.get().cmp(&other.get())
+    }
 }
 
 #[cfg(test)]
@@ -41,4 +97,56 @@ mod tests {
         a.set('b');
         assert_eq!(a.get(), 'b');
     }
+
+    #[test]
+    fn take() {
+        let v = Cell::new(vec![1, 2, 3]);
+        assert_eq!(v.take(), vec![1, 2, 3]);
+        assert_eq!(v.take(), Vec::<i32>::new());
+
+        let s = Cell::new(String::from("taken"));
+        assert_eq!(s.take(), "taken");
+        assert_eq!(s.take(), "");
+
+        let o = Cell::new(Some(Box::new(5)));
+        assert_eq!(o.take(), Some(Box::new(5)));
+        assert_eq!(o.take(), None);
+    }
+
+    #[test]
+    fn default() {
+        let c: Cell<Option<String>> = Cell::default();
+        assert_eq!(c.replace(Some(String::from("a"))), None);
+        assert_eq!(c.take().as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn try_update() {
+        let c = Cell::new(250u8);
+        assert!(
+            c.try_update(|v| v.checked_add(10).ok_or("overflow"))
+                .is_err()
+        );
+        assert_eq!(c.get(), 250);
+        assert!(c.try_update(|v| v.checked_add(5).ok_or("overflow")).is_ok());
+        assert_eq!(c.get(), 255);
+    }
+
+    #[test]
+    fn ord() {
+        let mut cells: Vec<_> = [3, 1, 2].map(Cell::new).into();
+        cells.sort();
+        assert_eq!(cells.iter().map(Cell::get).collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(Cell::new(1.0) < Cell::new(2.0));
+        assert!(Cell::new('a') == Cell::new('a'));
+    }
+
+    #[test]
+    fn compare_and_set() {
+        let c = Cell::new(1);
+        assert!(c.compare_and_set(1, 2));
+        assert_eq!(c.get(), 2);
+        assert!(!c.compare_and_set(1, 3));
+        assert_eq!(c.get(), 2);
+    }
 }
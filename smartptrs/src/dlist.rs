@@ -0,0 +1,162 @@
+use crate::rc::{Rc, Weak};
+use crate::refcell::RefCell;
+
+type Link<T> = Rc<RefCell<Node<T>>>;
+
+// next links own the nodes, prev links are weak so the list has no cycles
+struct Node<T> {
+    value: T,
+    next: Option<Link<T>>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+
+/// Handle to a node returned by `push_back`, used to remove it in O(1).
+/// It doesn't keep the node alive; once the node leaves the list it goes dead.
+pub struct NodeRef<T>(Weak<RefCell<Node<T>>>);
+
+pub struct DList<T> {
+    head: Option<Link<T>>,
+    tail: Option<Link<T>>,
+    len: usize,
+}
+
+impl<T> DList<T> {
+    pub fn new() -> DList<T> {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_back(&mut self, value: T) -> NodeRef<T> {
+        let node = Rc::new(RefCell::new(Node {
+            value,
+            next: None,
+            prev: self.tail.as_ref().map(Rc::downgrade),
+        }));
+        match self.tail.take() {
+            Some(tail) => tail.borrow_mut().next = Some(node.clone()),
+            None => self.head = Some(node.clone()),
+        }
+        let handle = NodeRef(Rc::downgrade(&node));
+        self.tail = Some(node);
+        self.len += 1;
+        handle
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head.take()?;
+        let next = head.borrow_mut().next.take();
+        match next {
+            Some(ref next) => next.borrow_mut().prev = None,
+            None => self.tail = None,
+        }
+        self.head = next;
+        self.len -= 1;
+        Some(Self::into_value(head))
+    }
+
+    /// Unlinks `node` from the list and returns its value, or `None` if it was already removed.
+    /// The handle must come from this list.
+    pub fn remove(&mut self, node: &NodeRef<T>) -> Option<T> {
+        let node = node.0.upgrade()?;
+        // take both links in one short borrow, then touch each neighbour separately,
+        // so no two borrow_muts are ever held at the same time
+        let (prev, next) = {
+            let mut node = node.borrow_mut();
+            (node.prev.take().and_then(|w| w.upgrade()), node.next.take())
+        };
+        match prev {
+            Some(ref prev) => prev.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match next {
+            Some(ref next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev,
+        }
+        self.len -= 1;
+        Some(Self::into_value(node))
+    }
+
+    fn into_value(node: Link<T>) -> T {
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().value,
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+}
+
+impl<T> Default for DList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for DList<T> {
+    fn drop(&mut self) {
+        // unlink iteratively; the default recursive drop can overflow the stack on long lists
+        while self.pop_front().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_work() {
+        let mut list = DList::new();
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn remove() {
+        let mut list = DList::new();
+        let a = list.push_back("a");
+        let b = list.push_back("b");
+        let c = list.push_back("c");
+
+        assert_eq!(list.remove(&b), Some("b"));
+        assert!(b.0.upgrade().is_none()); // freed, not leaked
+        assert_eq!(list.remove(&b), None);
+
+        // neighbours were relinked both ways
+        let a_next = a.0.upgrade().unwrap().borrow().next.clone().unwrap();
+        assert!(Rc::ptr_eq(&a_next, &c.0.upgrade().unwrap()));
+        let c_prev = c.0.upgrade().unwrap().borrow().prev.clone().unwrap();
+        assert!(Rc::ptr_eq(
+            &c_prev.upgrade().unwrap(),
+            &a.0.upgrade().unwrap()
+        ));
+        drop(a_next);
+
+        assert_eq!(list.remove(&c), Some("c"));
+        assert_eq!(list.remove(&a), Some("a"));
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn no_leak_on_drop() {
+        let mut list = DList::new();
+        let handles: Vec<_> = (0..100).map(|i| list.push_back(i)).collect();
+        list.remove(&handles[50]);
+        drop(list);
+        assert!(handles.iter().all(|h| h.0.upgrade().is_none()));
+    }
+}
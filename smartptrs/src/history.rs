@@ -0,0 +1,91 @@
+use crate::refcell::RefCell;
+
+/// Undo/redo over snapshots of an editor-like state. The last committed (or redone) state
+/// is the current one; `undo` steps back to the one before it.
+pub struct History<T> {
+    past: RefCell<Vec<T>>, // the current state is the last entry
+    future: RefCell<Vec<T>>,
+}
+
+impl<T: Clone> History<T> {
+    pub fn new() -> History<T> {
+        Self {
+            past: RefCell::new(Vec::new()),
+            future: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Records a new current state. Anything that could have been redone is dropped.
+    pub fn commit(&self, state: T) {
+        self.past.borrow_mut().push(state);
+        self.future.borrow_mut().clear();
+    }
+
+    pub fn current(&self) -> Option<T> {
+        self.past.borrow().last().cloned()
+    }
+
+    /// Steps back one state and returns the new current one, or `None` if there is no
+    /// earlier state to go back to.
+    pub fn undo(&self) -> Option<T> {
+        // each borrow_mut ends with its statement, so the two vectors are never
+        // mutably borrowed at the same time
+        if self.past.borrow().len() < 2 {
+            return None;
+        }
+        let undone = self.past.borrow_mut().pop()?;
+        self.future.borrow_mut().push(undone);
+        self.current()
+    }
+
+    /// Re-applies the last undone state and returns it.
+    pub fn redo(&self) -> Option<T> {
+        let redone = self.future.borrow_mut().pop()?;
+        self.past.borrow_mut().push(redone.clone());
+        Some(redone)
+    }
+}
+
+impl<T: Clone> Default for History<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let h = History::new();
+        assert_eq!(h.undo(), None);
+        assert_eq!(h.redo(), None);
+
+        h.commit("a");
+        h.commit("ab");
+        h.commit("abc");
+        assert_eq!(h.undo(), Some("ab"));
+        assert_eq!(h.undo(), Some("a"));
+        assert_eq!(h.undo(), None); // nothing before the first state
+        assert_eq!(h.current(), Some("a"));
+
+        assert_eq!(h.redo(), Some("ab"));
+        assert_eq!(h.redo(), Some("abc"));
+        assert_eq!(h.redo(), None);
+        assert_eq!(h.current(), Some("abc"));
+    }
+
+    #[test]
+    fn commit_clears_redo() {
+        let h = History::new();
+        h.commit(1);
+        h.commit(2);
+        assert_eq!(h.undo(), Some(1));
+
+        h.commit(3);
+        assert_eq!(h.redo(), None);
+        assert_eq!(h.undo(), Some(1));
+        assert_eq!(h.redo(), Some(3));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::cell::Cell;
+
+// !Sync, so IDs are only unique within one thread
+pub struct IdGen {
+    next: Cell<u64>,
+}
+
+impl IdGen {
+    pub const fn new() -> IdGen {
+        Self { next: Cell::new(0) }
+    }
+
+    /// Returns the next ID. Panics instead of wrapping when the IDs run out, so an ID is never reused.
+    pub fn next(&self) -> u64 {
+        let id = self.next.get();
+        self.next.set(id.checked_add(1).expect("IdGen overflowed"));
+        id
+    }
+}
+
+impl Default for IdGen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_work() {
+        let ids = IdGen::new();
+        let mut prev = ids.next();
+        for _ in 0..100 {
+            let id = ids.next();
+            assert!(id > prev);
+            prev = id;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let ids = IdGen {
+            next: Cell::new(u64::MAX),
+        };
+        ids.next();
+    }
+}
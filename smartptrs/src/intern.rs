@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::cell::Cell;
+use crate::rc::{Rc, Weak};
+use crate::refcell::RefCell;
+
+// T: Clone because the pool keys on its own copy of the value
+pub struct Interner<T> {
+    pool: RefCell<HashMap<T, Weak<T>>>,
+    sweep_at: Cell<usize>, // pool size that triggers the next cleanup of dead entries
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> {
+    pub fn new() -> Interner<T> {
+        Self {
+            pool: RefCell::new(HashMap::new()),
+            sweep_at: Cell::new(16),
+        }
+    }
+
+    pub fn intern(&self, value: T) -> Rc<T> {
+        let mut pool = self.pool.borrow_mut();
+        if let Some(rc) = pool.get(&value).and_then(Weak::upgrade) {
+            return rc;
+        }
+
+        if pool.len() >= self.sweep_at.get() {
+            pool.retain(|_, weak| weak.strong_count() > 0);
+            self.sweep_at.set((pool.len() * 2).max(16));
+        }
+
+        let rc = Rc::new(value.clone());
+        pool.insert(value, Rc::downgrade(&rc));
+        rc
+    }
+
+    pub fn len(&self) -> usize {
+        self.pool.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Hash + Eq + Clone> Default for Interner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_work() {
+        let interner = Interner::new();
+        let a = interner.intern(String::from("foo"));
+        let b = interner.intern(String::from("foo"));
+        let c = interner.intern(String::from("bar"));
+        assert!(Rc::ptr_eq(&a, &b));
+        assert!(!Rc::ptr_eq(&a, &c));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn dead_entries_swept() {
+        let interner = Interner::new();
+        for i in 0..100 {
+            drop(interner.intern(i));
+        }
+        let kept = interner.intern(-1);
+        assert!(interner.len() < 100);
+        assert!(Rc::ptr_eq(&kept, &interner.intern(-1)));
+    }
+}
@@ -0,0 +1,109 @@
+use std::cell::UnsafeCell;
+use std::ops::Deref;
+
+enum State<T, F> {
+    Uninit(F),
+    Init(T),
+    Initializing, // f is running, or it panicked and left the cell poisoned
+}
+
+/// A value computed by `f` on first access, like `std::cell::LazyCell`.
+///
+/// A `RefCell<Option<T>>` can't hand out a plain `&T` that outlives the borrow guard, so the
+/// state lives in an `UnsafeCell`. Once initialized it never changes again, which is what
+/// makes the returned references sound.
+// Send if T and F are Send, !Sync because of UnsafeCell
+pub struct LazyCell<T, F = fn() -> T> {
+    state: UnsafeCell<State<T, F>>,
+}
+
+impl<T, F: FnOnce() -> T> LazyCell<T, F> {
+    pub const fn new(f: F) -> LazyCell<T, F> {
+        Self {
+            state: UnsafeCell::new(State::Uninit(f)),
+        }
+    }
+
+    pub fn get(&self) -> &T {
+        // SAFETY: only read here; an initialized state is never written again, so the &T we
+        // hand out stays valid, and earlier ones aren't invalidated by later calls
+        match unsafe { &*self.state.get() } {
+            State::Init(value) => return value,
+            State::Initializing => panic!("LazyCell accessed during or after a failed init"),
+            State::Uninit(_) => {}
+        }
+        // SAFETY: !Sync, and while uninitialized no reference into the state exists; the
+        // Initializing marker makes a re-entrant get from f panic instead of getting here
+        let state = unsafe { &mut *self.state.get() };
+        let State::Uninit(f) = std::mem::replace(state, State::Initializing) else {
+            unreachable!()
+        };
+        let value = f();
+        // re-fetch instead of reusing `state`, f may have read the cell in the meantime
+        let state = unsafe { &mut *self.state.get() };
+        *state = State::Init(value);
+        match state {
+            State::Init(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn into_inner(self) -> Result<T, F> {
+        match self.state.into_inner() {
+            State::Init(value) => Ok(value),
+            State::Uninit(f) => Err(f),
+            State::Initializing => panic!("LazyCell accessed during or after a failed init"),
+        }
+    }
+}
+
+impl<T, F: FnOnce() -> T> Deref for LazyCell<T, F> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    #[test]
+    fn init_once() {
+        let calls = Cell::new(0);
+        let lazy = LazyCell::new(|| {
+            calls.set(calls.get() + 1);
+            String::from("lazy")
+        });
+        assert_eq!(calls.get(), 0);
+        assert_eq!(lazy.get(), "lazy");
+        let a: &String = lazy.get();
+        let b: &String = &lazy;
+        assert!(std::ptr::eq(a, b));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(lazy.into_inner().ok().as_deref(), Some("lazy"));
+    }
+
+    #[test]
+    fn fn_pointer_default() {
+        fn answer() -> i32 {
+            42
+        }
+        let lazy: LazyCell<i32> = LazyCell::new(answer);
+        assert_eq!(*lazy, 42);
+        assert!(LazyCell::<i32>::new(answer).into_inner().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn reentrant_init() {
+        type Lazy = LazyCell<i32, Box<dyn FnOnce() -> i32>>;
+        // leaked so the initializer can point back at its own cell
+        let slot: &'static Cell<Option<&'static Lazy>> = Box::leak(Box::new(Cell::new(None)));
+        let init: Box<dyn FnOnce() -> i32> = Box::new(|| *slot.get().unwrap().get());
+        let lazy: &'static Lazy = Box::leak(Box::new(LazyCell::new(init)));
+        slot.set(Some(lazy));
+        lazy.get();
+    }
+}
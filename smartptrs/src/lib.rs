@@ -1,3 +1,13 @@
 pub mod cell;
+pub mod dlist;
+pub mod history;
+pub mod id;
+pub mod intern;
+pub mod lazy;
+pub mod memoize;
 pub mod rc;
+pub mod rc_cell;
+pub mod rc_slice;
 pub mod refcell;
+pub mod shared_str;
+pub mod state_machine;
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::refcell::RefCell;
+
+pub struct Memoize<K, V> {
+    cache: RefCell<HashMap<K, V>>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memoize<K, V> {
+    pub fn new() -> Memoize<K, V> {
+        Self {
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn get_or_compute(&self, key: K, f: impl FnOnce() -> V) -> V {
+        if let Some(v) = self.cache.borrow().get(&key) {
+            return v.clone();
+        }
+        // no borrow is held while f runs, so f may call back into this Memoize
+        let v = f();
+        self.cache.borrow_mut().entry(key).or_insert(v).clone()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memoize<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_work() {
+        let calls = std::cell::Cell::new(0);
+        let memo = Memoize::new();
+        for _ in 0..3 {
+            for key in ["a", "bb", "ccc"] {
+                let len = memo.get_or_compute(key, || {
+                    calls.set(calls.get() + 1);
+                    key.len()
+                });
+                assert_eq!(len, key.len());
+            }
+        }
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn reentrant() {
+        fn fib(memo: &Memoize<u64, u64>, n: u64) -> u64 {
+            memo.get_or_compute(n, || match n {
+                0 | 1 => n,
+                _ => fib(memo, n - 1) + fib(memo, n - 2),
+            })
+        }
+
+        let memo = Memoize::new();
+        assert_eq!(fib(&memo, 50), 12586269025);
+    }
+}
@@ -1,34 +1,235 @@
+use std::alloc::{Layout, alloc, handle_alloc_error};
 use std::marker::PhantomData;
-use std::ptr::NonNull;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::pin::Pin;
+use std::ptr::{self, NonNull};
 
 use crate::cell::Cell;
+use crate::refcell::RefCell;
 
 // !Send and !Sync
-pub struct Rc<T> {
+pub struct Rc<T: ?Sized> {
     ptr: NonNull<RcInner<T>>,
     phantom: PhantomData<RcInner<T>>, // drop check
 }
 
-struct RcInner<T> {
+#[repr(C)] // keep the header layout identical for sized and unsized T
+struct RcInner<T: ?Sized> {
     strong: Cell<usize>,
-    value: T,
+    weak: Cell<usize>,      // all strong refs together hold one weak ref
+    value: ManuallyDrop<T>, // dropped by the last Rc, the allocation by the last Weak
 }
 
 impl<T> Rc<T> {
     pub fn new(value: T) -> Rc<T> {
+        let inner = Box::new(RcInner {
+            strong: Cell::new(1),
+            weak: Cell::new(1),
+            value: ManuallyDrop::new(value),
+        });
+        unsafe { Rc::from_inner(NonNull::from(Box::leak(inner))) }
+    }
+
+    /// Allocates without initializing, so a large value can be built in place on the heap
+    /// instead of on the stack first.
+    pub fn new_uninit() -> Rc<MaybeUninit<T>> {
+        let layout = Layout::new::<RcInner<MaybeUninit<T>>>();
+        unsafe {
+            let inner = alloc(layout) as *mut RcInner<MaybeUninit<T>>;
+            if inner.is_null() {
+                handle_alloc_error(layout);
+            }
+            ptr::write(&raw mut (*inner).strong, Cell::new(1));
+            ptr::write(&raw mut (*inner).weak, Cell::new(1));
+            Rc::from_inner(NonNull::new_unchecked(inner))
+        }
+    }
+
+    pub fn pin(value: T) -> Pin<Rc<T>> {
+        // SAFETY: the value lives on the heap and Pin<Rc<T>> never gives the Rc back, so
+        // get_mut and try_unwrap can't be reached to move it out
+        unsafe { Pin::new_unchecked(Rc::new(value)) }
+    }
+
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        let inner = unsafe { this.ptr.as_ref() };
+        if inner.strong.get() != 1 {
+            return Err(this);
+        }
+        inner.strong.set(0);
+        // SAFETY: we were the last strong ref, so nobody else can reach the value
+        let value = unsafe { ManuallyDrop::take(&mut (*this.ptr.as_ptr()).value) };
+        drop(Weak { ptr: this.ptr });
+        std::mem::forget(this);
+        Ok(value)
+    }
+
+    /// Projects into part of the value. The returned `RcRef` keeps the whole allocation
+    /// alive, so the original `Rc` can be dropped.
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> RcRef<U>
+    where
+        T: 'static, // the owner is erased, so it must not borrow anything
+        F: FnOnce(&T) -> &U,
+    {
+        let value = NonNull::from(f(&*this));
+        let this = ManuallyDrop::new(this);
+        RcRef {
+            owner: this.ptr.cast(),
+            clone_owner: erased_clone::<T>,
+            drop_owner: erased_drop::<T>,
+            value,
+        }
+    }
+}
+
+impl<T> Rc<MaybeUninit<T>> {
+    /// Initializes the value. Panics if the `Rc` has been cloned or downgraded.
+    pub fn write(&mut self, value: T) -> &mut T {
+        Rc::get_mut(self)
+            .expect("Rc::write on a shared Rc")
+            .write(value)
+    }
+
+    /// # Safety
+    ///
+    /// The value must have been fully initialized, e.g. with `write`.
+    pub unsafe fn assume_init(self) -> Rc<T> {
+        let this = ManuallyDrop::new(self);
+        // MaybeUninit<T> has the layout of T, and RcInner is repr(C)
+        Rc {
+            ptr: this.ptr.cast(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+unsafe fn erased_clone<T>(owner: NonNull<()>) {
+    let inner = unsafe { owner.cast::<RcInner<T>>().as_ref() };
+    inner.strong.set(inner.strong.get() + 1);
+}
+
+unsafe fn erased_drop<T>(owner: NonNull<()>) {
+    drop(Rc::<T> {
+        ptr: owner.cast(),
+        phantom: PhantomData,
+    });
+}
+
+// a strong ref to some Rc<T> with T erased, plus a pointer into its value
+// !Send and !Sync
+pub struct RcRef<U: ?Sized> {
+    owner: NonNull<()>,
+    clone_owner: unsafe fn(NonNull<()>),
+    drop_owner: unsafe fn(NonNull<()>),
+    value: NonNull<U>,
+}
+
+impl<U: ?Sized> Clone for RcRef<U> {
+    fn clone(&self) -> Self {
+        unsafe { (self.clone_owner)(self.owner) };
+        Self { ..*self }
+    }
+}
+
+impl<U: ?Sized> std::ops::Deref for RcRef<U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: value points into the heap allocation that owner keeps alive
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<U: ?Sized> Drop for RcRef<U> {
+    fn drop(&mut self) {
+        unsafe { (self.drop_owner)(self.owner) };
+    }
+}
+
+impl<T: ?Sized> Rc<T> {
+    // takes ownership of a freshly allocated RcInner with both counts at 1
+    unsafe fn from_inner(ptr: NonNull<RcInner<T>>) -> Self {
+        #[cfg(feature = "leak-detection")]
+        live::insert(ptr.cast());
         Self {
-            ptr: unsafe {
-                NonNull::new_unchecked(Box::into_raw(Box::new(RcInner {
-                    strong: Cell::new(1),
-                    value,
-                })))
-            },
+            ptr,
             phantom: PhantomData,
         }
     }
+
+    pub fn downgrade(this: &Self) -> Weak<T> {
+        let inner = unsafe { this.ptr.as_ref() };
+        inner.weak.set(inner.weak.get() + 1);
+        Weak { ptr: this.ptr }
+    }
+
+    pub fn strong_count(this: &Self) -> usize {
+        unsafe { this.ptr.as_ref() }.strong.get()
+    }
+
+    /// Mutable access if no other `Rc` or `Weak` points to the value.
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if Rc::strong_count(this) == 1 && Rc::weak_count(this) == 0 {
+            // SAFETY: we are the only handle, and &mut self keeps it that way
+            Some(unsafe { &mut this.ptr.as_mut().value })
+        } else {
+            None
+        }
+    }
+
+    pub fn weak_count(this: &Self) -> usize {
+        unsafe { this.ptr.as_ref() }.weak.get() - 1
+    }
+
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::addr_eq(this.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+}
+
+impl From<&str> for Rc<str> {
+    fn from(s: &str) -> Self {
+        // header and bytes share one allocation, laid out exactly as Box<RcInner<str>> expects
+        let (layout, _) = Layout::new::<RcInner<()>>()
+            .extend(Layout::array::<u8>(s.len()).unwrap())
+            .unwrap();
+        let layout = layout.pad_to_align();
+        unsafe {
+            let mem = alloc(layout);
+            if mem.is_null() {
+                handle_alloc_error(layout);
+            }
+            let inner = ptr::slice_from_raw_parts_mut(mem, s.len()) as *mut RcInner<str>;
+            ptr::write(&raw mut (*inner).strong, Cell::new(1));
+            ptr::write(&raw mut (*inner).weak, Cell::new(1));
+            ptr::copy_nonoverlapping(s.as_ptr(), (&raw mut (*inner).value).cast::<u8>(), s.len());
+            Self::from_inner(NonNull::new_unchecked(inner))
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Rc<[T]> {
+    fn from(mut v: Vec<T>) -> Self {
+        // same single-allocation layout as Rc<str>, with the elements moved out of the Vec
+        let (layout, _) = Layout::new::<RcInner<()>>()
+            .extend(Layout::array::<T>(v.len()).unwrap())
+            .unwrap();
+        let layout = layout.pad_to_align();
+        unsafe {
+            let mem = alloc(layout);
+            if mem.is_null() {
+                handle_alloc_error(layout);
+            }
+            let inner =
+                ptr::slice_from_raw_parts_mut(mem.cast::<T>(), v.len()) as *mut RcInner<[T]>;
+            ptr::write(&raw mut (*inner).strong, Cell::new(1));
+            ptr::write(&raw mut (*inner).weak, Cell::new(1));
+            ptr::copy_nonoverlapping(v.as_ptr(), (&raw mut (*inner).value).cast::<T>(), v.len());
+            v.set_len(0); // the elements are owned by the Rc now, only free the buffer
+            Self::from_inner(NonNull::new_unchecked(inner))
+        }
+    }
 }
 
-impl<T> Clone for Rc<T> {
+impl<T: ?Sized> Clone for Rc<T> {
     fn clone(&self) -> Self {
         let inner = unsafe { self.ptr.as_ref() };
         inner.strong.set(inner.strong.get() + 1);
@@ -39,25 +240,153 @@ impl<T> Clone for Rc<T> {
     }
 }
 
-impl<T> std::ops::Deref for Rc<T> {
+impl<T: ?Sized> std::ops::Deref for Rc<T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         unsafe { &self.ptr.as_ref().value }
     }
 }
 
-impl<T> Drop for Rc<T> {
+impl<T: ?Sized> Drop for Rc<T> {
     fn drop(&mut self) {
         let inner = unsafe { self.ptr.as_ref() };
         let cnt = inner.strong.get();
         if cnt == 1 {
-            drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+            inner.strong.set(0);
+            // SAFETY: we were the last strong ref, so no &T into the value is left
+            unsafe { ManuallyDrop::drop(&mut (*self.ptr.as_ptr()).value) };
+            // release the weak ref held on behalf of all strong refs
+            drop(Weak { ptr: self.ptr });
         } else {
             inner.strong.set(cnt - 1);
         }
     }
 }
 
+// !Send and !Sync
+pub struct Weak<T: ?Sized> {
+    ptr: NonNull<RcInner<T>>,
+}
+
+impl<T: ?Sized> Weak<T> {
+    pub fn upgrade(&self) -> Option<Rc<T>> {
+        let inner = unsafe { self.ptr.as_ref() };
+        let cnt = inner.strong.get();
+        if cnt == 0 {
+            return None;
+        }
+        inner.strong.set(cnt + 1);
+        Some(Rc {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        })
+    }
+
+    pub fn strong_count(&self) -> usize {
+        unsafe { self.ptr.as_ref() }.strong.get()
+    }
+}
+
+impl<T: ?Sized> Clone for Weak<T> {
+    fn clone(&self) -> Self {
+        let inner = unsafe { self.ptr.as_ref() };
+        inner.weak.set(inner.weak.get() + 1);
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T: ?Sized> Drop for Weak<T> {
+    fn drop(&mut self) {
+        let inner = unsafe { self.ptr.as_ref() };
+        let cnt = inner.weak.get();
+        if cnt == 1 {
+            // value is ManuallyDrop, so this only frees the allocation
+            #[cfg(feature = "leak-detection")]
+            live::remove(self.ptr.cast());
+            drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+        } else {
+            inner.weak.set(cnt - 1);
+        }
+    }
+}
+
+// addresses of this thread's RcInner allocations; Rc is !Send, so a thread-local set sees
+// every allocation from creation to free
+#[cfg(feature = "leak-detection")]
+mod live {
+    use std::collections::HashSet;
+    use std::ptr::NonNull;
+
+    use crate::refcell::RefCell;
+
+    thread_local! {
+        static LIVE: RefCell<HashSet<NonNull<()>>> = RefCell::new(HashSet::new());
+    }
+
+    pub(super) fn insert(ptr: NonNull<()>) {
+        LIVE.with(|live| live.borrow_mut().insert(ptr));
+    }
+
+    pub(super) fn remove(ptr: NonNull<()>) {
+        // try_with: a Weak may be dropped by another thread-local's destructor after ours
+        let _ = LIVE.try_with(|live| live.borrow_mut().remove(&ptr));
+    }
+
+    pub(super) fn count() -> usize {
+        LIVE.with(|live| live.borrow().len())
+    }
+}
+
+/// How many `Rc` allocations made on this thread haven't been freed yet. An allocation is
+/// freed once its last `Rc` and `Weak` are gone, so a steadily growing count after the
+/// values should have been dropped points at a reference cycle.
+#[cfg(feature = "leak-detection")]
+pub fn rc_live_count() -> usize {
+    live::count()
+}
+
+/// `get`/`set`/`update` straight on an `Rc<Cell<T>>`, so shared counters and flags read
+/// as `rc.update(..)` instead of going through `(**rc)`.
+pub trait RcCellExt<T: Copy> {
+    fn get(&self) -> T;
+    fn set(&self, val: T);
+    fn update(&self, f: impl FnOnce(T) -> T);
+}
+
+impl<T: Copy> RcCellExt<T> for Rc<Cell<T>> {
+    fn get(&self) -> T {
+        Cell::get(self)
+    }
+
+    fn set(&self, val: T) {
+        Cell::set(self, val)
+    }
+
+    fn update(&self, f: impl FnOnce(T) -> T) {
+        Cell::set(self, f(Cell::get(self)))
+    }
+}
+
+pub fn set_shared<T: Copy>(rc: &Rc<Cell<T>>, val: T) {
+    rc.set(val)
+}
+
+/// Shorthand for `weak.upgrade().map(|rc| f(&mut rc.borrow_mut()))`, the usual way to
+/// touch a neighbour through a back-link in graph code.
+pub trait WeakRefCellExt<T> {
+    /// Runs `f` on the target, or returns `None` if it has been dropped. Panics like
+    /// `borrow_mut` if the target is already borrowed.
+    fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R>;
+}
+
+impl<T> WeakRefCellExt<T> for Weak<RefCell<T>> {
+    fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let rc = self.upgrade()?;
+        let mut value = rc.borrow_mut();
+        Some(f(&mut value))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +398,177 @@ mod tests {
         a.set(19);
         assert_eq!(19, b.get());
     }
+
+    #[test]
+    fn pin() {
+        let a = Rc::pin(42);
+        let b = a.clone();
+        assert_eq!(*a, 42);
+        assert_eq!(*b.as_ref().get_ref(), 42);
+    }
+
+    #[test]
+    fn weak() {
+        let a = Rc::new(String::from("weak"));
+        let w = Rc::downgrade(&a);
+        assert_eq!(Rc::weak_count(&a), 1);
+
+        let b = w.upgrade().unwrap();
+        assert!(Rc::ptr_eq(&a, &b));
+        assert_eq!(Rc::strong_count(&a), 2);
+
+        drop(a);
+        drop(b);
+        assert!(w.upgrade().is_none());
+        assert_eq!(w.strong_count(), 0);
+    }
+
+    #[test]
+    fn rc_str() {
+        let a: Rc<str> = Rc::from("shared string");
+        let clones: Vec<_> = (0..1000).map(|_| a.clone()).collect();
+        assert_eq!(Rc::strong_count(&a), 1001);
+        for s in &clones {
+            assert_eq!(&**s, "shared string");
+            assert!(Rc::ptr_eq(s, &a));
+        }
+        drop(clones);
+
+        let w = Rc::downgrade(&a);
+        drop(a);
+        assert!(w.upgrade().is_none());
+
+        let empty: Rc<str> = Rc::from("");
+        assert_eq!(&*empty, "");
+    }
+
+    #[test]
+    fn rc_slice() {
+        let value = Rc::new(());
+        let a: Rc<[Rc<()>]> = Rc::from(vec![value.clone(), value.clone()]);
+        let b = a.clone();
+        assert_eq!(a.len(), 2);
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(a);
+        drop(b);
+        assert_eq!(Rc::strong_count(&value), 1); // the elements were dropped exactly once
+
+        let empty: Rc<[u64]> = Rc::from(Vec::new());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn try_unwrap() {
+        let a = Rc::new(String::from("only"));
+        let b = a.clone();
+        let a = Rc::try_unwrap(a).unwrap_err();
+        drop(b);
+        let w = Rc::downgrade(&a);
+        assert_eq!(Rc::try_unwrap(a).ok().as_deref(), Some("only"));
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    fn map() {
+        let a = Rc::new((1, String::from("field")));
+        let w = Rc::downgrade(&a);
+
+        let s = Rc::map(a, |(_, s)| s);
+        let t = s.clone();
+        assert_eq!(*s, "field");
+        assert_eq!(w.strong_count(), 2);
+
+        drop(s);
+        assert_eq!(t.len(), 5);
+        drop(t);
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    fn rc_cell_ext() {
+        let a = Rc::new(Cell::new(1));
+        let b = a.clone();
+
+        a.set(2);
+        assert_eq!(b.get(), 2);
+        b.update(|x| x * 10);
+        assert_eq!(a.get(), 20);
+        set_shared(&b, 7);
+        assert_eq!(a.get(), 7);
+    }
+
+    #[test]
+    fn weak_with_mut() {
+        let a = Rc::new(RefCell::new(vec![1]));
+        let w = Rc::downgrade(&a);
+
+        assert_eq!(
+            w.with_mut(|v| {
+                v.push(2);
+                v.len()
+            }),
+            Some(2)
+        );
+        assert_eq!(*a.borrow(), [1, 2]);
+
+        drop(a);
+        assert_eq!(w.with_mut(|v| v.push(3)), None);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut a = Rc::new(1);
+        *Rc::get_mut(&mut a).unwrap() += 1;
+        let b = a.clone();
+        assert!(Rc::get_mut(&mut a).is_none());
+        drop(b);
+        let w = Rc::downgrade(&a);
+        assert!(Rc::get_mut(&mut a).is_none());
+        drop(w);
+        assert_eq!(*Rc::get_mut(&mut a).unwrap(), 2);
+    }
+
+    #[test]
+    fn new_uninit() {
+        let mut a = Rc::<String>::new_uninit();
+        a.write(String::from("late"));
+        let a = unsafe { a.assume_init() };
+        assert_eq!(*a, "late");
+
+        // far bigger than a test thread's stack, so it has to be filled in place
+        const N: usize = 16 << 20;
+        let mut big = Rc::<[u8; N]>::new_uninit();
+        let p = Rc::get_mut(&mut big).unwrap().as_mut_ptr().cast::<u8>();
+        for i in 0..N {
+            unsafe { p.add(i).write(i as u8) };
+        }
+        let big = unsafe { big.assume_init() };
+        assert_eq!(big[N - 1], (N - 1) as u8);
+        assert_eq!(big[300], 44);
+    }
+
+    #[cfg(feature = "leak-detection")]
+    #[test]
+    fn live_count() {
+        use crate::refcell::RefCell;
+
+        let before = rc_live_count();
+        let a = Rc::new(1);
+        let b = a.clone();
+        let s: Rc<str> = Rc::from("tracked");
+        let w = Rc::downgrade(&a);
+        assert_eq!(rc_live_count(), before + 2);
+        drop((a, b, s));
+        assert_eq!(rc_live_count(), before + 1); // the Weak still holds the allocation
+        drop(w);
+        assert_eq!(rc_live_count(), before);
+
+        // a two-node cycle keeps both allocations alive after the handles are gone
+        struct Node(RefCell<Option<Rc<Node>>>);
+        let x = Rc::new(Node(RefCell::new(None)));
+        let y = Rc::new(Node(RefCell::new(Some(x.clone()))));
+        *x.0.borrow_mut() = Some(y.clone());
+        drop((x, y));
+        assert_eq!(rc_live_count(), before + 2);
+    }
 }
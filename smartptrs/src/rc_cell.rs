@@ -0,0 +1,115 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use crate::cell::Cell;
+use crate::refcell::{Ref, RefCell, RefMut};
+
+/// `Rc<RefCell<T>>` as a single type: the count, the borrow flag and the value share one
+/// allocation and one pointer hop. `Rc<RefCell<T>>` is laid out that way too, but also carries
+/// a weak count that this type drops, and `borrow` here needs no `(*rc)` to get past the Rc.
+// !Send and !Sync
+pub struct RcCell<T> {
+    ptr: NonNull<RcCellInner<T>>,
+    phantom: PhantomData<RcCellInner<T>>, // drop check
+}
+
+struct RcCellInner<T> {
+    strong: Cell<usize>,
+    cell: RefCell<T>,
+}
+
+impl<T> RcCell<T> {
+    pub fn new(value: T) -> RcCell<T> {
+        let inner = Box::new(RcCellInner {
+            strong: Cell::new(1),
+            cell: RefCell::new(value),
+        });
+        Self {
+            ptr: NonNull::from(Box::leak(inner)),
+            phantom: PhantomData,
+        }
+    }
+
+    fn inner(&self) -> &RcCellInner<T> {
+        // SAFETY: the allocation lives as long as any RcCell points to it
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Panics if the value is mutably borrowed through any clone.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.inner().cell.borrow()
+    }
+
+    /// Panics if the value is borrowed at all through any clone.
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        self.inner().cell.borrow_mut()
+    }
+
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+}
+
+impl<T> Clone for RcCell<T> {
+    fn clone(&self) -> Self {
+        let strong = &self.inner().strong;
+        strong.set(strong.get() + 1);
+        Self {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for RcCell<T> {
+    fn drop(&mut self) {
+        let strong = &self.inner().strong;
+        let cnt = strong.get();
+        if cnt == 1 {
+            // SAFETY: we were the last ref, and a live borrow would hold one too
+            drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+        } else {
+            strong.set(cnt - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_borrows() {
+        let a = RcCell::new(vec![1]);
+        let b = a.clone();
+        a.borrow_mut().push(2);
+        b.borrow_mut().push(3);
+        assert_eq!(*a.borrow(), [1, 2, 3]);
+
+        // shared borrows through different clones coexist
+        let ra = a.borrow();
+        let rb = b.borrow();
+        assert_eq!(ra.len(), rb.len());
+        assert!(RcCell::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    #[should_panic]
+    fn borrow_conflict() {
+        let a = RcCell::new(1);
+        let b = a.clone();
+        let _r = a.borrow();
+        let _w = b.borrow_mut();
+    }
+
+    #[test]
+    fn drops_once() {
+        let item = std::rc::Rc::new(());
+        let a = RcCell::new(std::rc::Rc::clone(&item));
+        let b = a.clone();
+        drop(a);
+        assert_eq!(std::rc::Rc::strong_count(&item), 2);
+        drop(b);
+        assert_eq!(std::rc::Rc::strong_count(&item), 1);
+    }
+}
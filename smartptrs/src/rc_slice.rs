@@ -0,0 +1,118 @@
+use std::ops::{Bound, Deref, Range, RangeBounds};
+
+use crate::rc::Rc;
+
+/// A view into part of a shared `Rc<[T]>`, like `bytes::Bytes` for any `T`. Slicing a view
+/// only bumps the count; every view keeps the whole backing slice alive.
+pub struct RcSlice<T> {
+    rc: Rc<[T]>,
+    range: Range<usize>,
+}
+
+impl<T> RcSlice<T> {
+    pub fn new(rc: Rc<[T]>) -> RcSlice<T> {
+        let range = 0..rc.len();
+        Self { rc, range }
+    }
+
+    /// A new view of `range`, relative to this one. Panics if it's out of bounds, just like
+    /// indexing a slice.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> RcSlice<T> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflows usize"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflows usize"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end,
+            "slice index starts at {start} but ends at {end}"
+        );
+        assert!(
+            end <= len,
+            "range end index {end} out of range for slice of length {len}"
+        );
+        Self {
+            rc: self.rc.clone(),
+            range: self.range.start + start..self.range.start + end,
+        }
+    }
+
+    /// The whole backing slice this view points into.
+    pub fn backing(&self) -> &Rc<[T]> {
+        &self.rc
+    }
+}
+
+impl<T> Clone for RcSlice<T> {
+    fn clone(&self) -> Self {
+        Self {
+            rc: self.rc.clone(),
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<T> Deref for RcSlice<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.rc[self.range.clone()]
+    }
+}
+
+impl<T> From<Vec<T>> for RcSlice<T> {
+    fn from(v: Vec<T>) -> Self {
+        RcSlice::new(Rc::from(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_views() {
+        let all = RcSlice::from(vec![0, 1, 2, 3, 4, 5]);
+        let left = all.slice(..4);
+        let right = all.slice(2..);
+        let middle = right.slice(1..=2);
+
+        assert_eq!(*left, [0, 1, 2, 3]);
+        assert_eq!(*right, [2, 3, 4, 5]);
+        assert_eq!(*middle, [3, 4]);
+        assert_eq!(*all.slice(3..3), []);
+
+        assert_eq!(Rc::strong_count(all.backing()), 4);
+        assert!(Rc::ptr_eq(left.backing(), middle.backing()));
+        drop(all);
+        drop(left);
+        assert_eq!(Rc::strong_count(right.backing()), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let all = RcSlice::from(vec![0, 1, 2]);
+        let tail = all.slice(1..);
+        let _ = tail.slice(..3);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end overflows usize")]
+    fn inclusive_end_overflow() {
+        let all = RcSlice::from(vec![0, 1, 2]);
+        let _ = all.slice(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start overflows usize")]
+    fn excluded_start_overflow() {
+        let all = RcSlice::from(vec![0, 1, 2]);
+        let _ = all.slice((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+}
@@ -5,13 +5,14 @@ use std::ptr::NonNull;
 use crate::cell::Cell;
 
 #[derive(Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
 enum BorrowState {
     UnBorrow,
     SharedBorrow(usize),
-    ExclusiveBorrow,
+    ExclusiveBorrow(usize), // number of RefMuts sharing the borrow after map_split
 }
 
-// Send if T: Send !Sync
+// Send if T: Send, like std; !Sync comes from UnsafeCell, see tests/ui/refcell_not_sync.rs
 pub struct RefCell<T> {
     value: UnsafeCell<T>,
     state: Cell<BorrowState>,
@@ -25,6 +26,11 @@ impl<T> RefCell<T> {
         }
     }
 
+    pub fn into_inner(self) -> T {
+        // no borrow can be alive since we own self
+        self.value.into_inner()
+    }
+
     pub fn borrow(&self) -> Ref<'_, T> {
         match self.state.get() {
             BorrowState::UnBorrow => {
@@ -41,7 +47,7 @@ impl<T> RefCell<T> {
                     state: &self.state,
                 }
             }
-            BorrowState::ExclusiveBorrow => {
+            BorrowState::ExclusiveBorrow(_) => {
                 panic!("fuck you no way!")
             }
         }
@@ -50,20 +56,41 @@ impl<T> RefCell<T> {
     pub fn borrow_mut(&self) -> RefMut<'_, T> {
         match self.state.get() {
             BorrowState::UnBorrow => {
-                self.state.set(BorrowState::ExclusiveBorrow);
+                self.state.set(BorrowState::ExclusiveBorrow(1));
                 RefMut {
                     value: unsafe { NonNull::new_unchecked(self.value.get()) },
                     state: &self.state,
                     _marker: PhantomData,
                 }
             }
-            BorrowState::SharedBorrow(_) | BorrowState::ExclusiveBorrow => {
+            BorrowState::SharedBorrow(_) | BorrowState::ExclusiveBorrow(_) => {
                 panic!("fuck you no way!")
             }
         }
     }
 }
 
+// comparisons borrow both sides, so like std they panic if either is mutably borrowed
+impl<T: PartialEq> PartialEq for RefCell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        *self.borrow() == *other.borrow()
+    }
+}
+
+impl<T: Eq> Eq for RefCell<T> {}
+
+impl<T: PartialOrd> PartialOrd for RefCell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.borrow().partial_cmp(&*other.borrow())
+    }
+}
+
+impl<T: Ord> Ord for RefCell<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.borrow().cmp(&*other.borrow())
+    }
+}
+
 pub struct Ref<'a, T: 'a> {
     value: NonNull<T>,
     state: &'a Cell<BorrowState>,
@@ -79,7 +106,7 @@ impl<'a, T: 'a> std::ops::Deref for Ref<'a, T> {
 impl<'a, T: 'a> Drop for Ref<'a, T> {
     fn drop(&mut self) {
         match self.state.get() {
-            BorrowState::UnBorrow | BorrowState::ExclusiveBorrow => unreachable!(),
+            BorrowState::UnBorrow | BorrowState::ExclusiveBorrow(_) => unreachable!(),
             BorrowState::SharedBorrow(count) => {
                 if count == 1 {
                     self.state.set(BorrowState::UnBorrow);
@@ -91,32 +118,65 @@ impl<'a, T: 'a> Drop for Ref<'a, T> {
     }
 }
 
-pub struct RefMut<'a, T: 'a> {
+pub struct RefMut<'a, T: ?Sized + 'a> {
     value: NonNull<T>,
     state: &'a Cell<BorrowState>,
     _marker: PhantomData<&'a mut T>, // invariance need
 }
 
-impl<'a, T: 'a> std::ops::Deref for RefMut<'a, T> {
+impl<'a, T: ?Sized + 'a> std::ops::Deref for RefMut<'a, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         unsafe { self.value.as_ref() }
     }
 }
 
-impl<'a, T: 'a> std::ops::DerefMut for RefMut<'a, T> {
+impl<'a, T: ?Sized + 'a> std::ops::DerefMut for RefMut<'a, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe { self.value.as_mut() }
     }
 }
 
-impl<'a, T: 'a> Drop for RefMut<'a, T> {
+impl<'a, T: ?Sized + 'a> RefMut<'a, T> {
+    pub fn map_split<U: ?Sized, V: ?Sized, F>(orig: Self, f: F) -> (RefMut<'a, U>, RefMut<'a, V>)
+    where
+        F: FnOnce(&mut T) -> (&mut U, &mut V),
+    {
+        let state = orig.state;
+        let BorrowState::ExclusiveBorrow(count) = state.get() else {
+            unreachable!()
+        };
+        let mut value = orig.value;
+        // SAFETY: we hold the exclusive borrow, and f returns two disjoint parts of it.
+        // orig stays alive while f runs, so a panic in f still releases the borrow
+        let (u, v) = f(unsafe { value.as_mut() });
+        std::mem::forget(orig); // its share of the borrow is handed to the two halves
+        state.set(BorrowState::ExclusiveBorrow(count + 1));
+        (
+            RefMut {
+                value: NonNull::from(u),
+                state,
+                _marker: PhantomData,
+            },
+            RefMut {
+                value: NonNull::from(v),
+                state,
+                _marker: PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T: ?Sized + 'a> Drop for RefMut<'a, T> {
     fn drop(&mut self) {
         match self.state.get() {
             BorrowState::UnBorrow | BorrowState::SharedBorrow(_) => unreachable!(),
-            BorrowState::ExclusiveBorrow => {
+            BorrowState::ExclusiveBorrow(1) => {
                 self.state.set(BorrowState::UnBorrow);
             }
+            BorrowState::ExclusiveBorrow(count) => {
+                self.state.set(BorrowState::ExclusiveBorrow(count - 1));
+            }
         }
     }
 }
@@ -151,4 +211,80 @@ mod tests {
         let b = z.borrow_mut();
         println!("{} {}", a.len(), b.len());
     }
+
+    #[test]
+    fn send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<RefCell<String>>();
+
+        let z = RefCell::new(vec![1]);
+        let z = std::thread::spawn(move || {
+            z.borrow_mut().push(2);
+            z
+        })
+        .join()
+        .unwrap();
+        assert_eq!(*z.borrow(), [1, 2]);
+    }
+
+    #[test]
+    fn map_split() {
+        let z = RefCell::new([1, 2, 3, 4]);
+        {
+            let (mut left, mut right) =
+                RefMut::map_split(z.borrow_mut(), |arr| arr.split_at_mut(2));
+            left[0] = 10;
+            right[1] = 40;
+            assert_eq!(*left, [10, 2]);
+            assert_eq!(*right, [3, 40]);
+
+            drop(left);
+            right[0] = 30; // the other half still holds the exclusive borrow
+        }
+        assert_eq!(*z.borrow(), [10, 2, 30, 40]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_split_keeps_exclusive() {
+        let z = RefCell::new([1, 2, 3, 4]);
+        let (left, _right) = RefMut::map_split(z.borrow_mut(), |arr| arr.split_at_mut(2));
+        drop(left);
+        let _ = z.borrow();
+    }
+
+    #[test]
+    fn map_split_panic_releases_borrow() {
+        let z = RefCell::new([1, 2]);
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            RefMut::map_split(z.borrow_mut(), |_| -> (&mut [i32], &mut [i32]) {
+                panic!("split failed")
+            })
+        }));
+        assert!(res.is_err());
+        z.borrow_mut()[0] = 10;
+        assert_eq!(*z.borrow(), [10, 2]);
+    }
+
+    #[test]
+    fn ord() {
+        let mut cells: Vec<_> = [3, 1, 2].map(RefCell::new).into();
+        cells.sort();
+        let sorted: Vec<_> = cells.into_iter().map(RefCell::into_inner).collect();
+        assert_eq!(sorted, [1, 2, 3]);
+
+        let a = RefCell::new(String::from("a"));
+        let b = RefCell::new(String::from("b"));
+        assert!(a < b);
+        assert!(a == a); // two shared borrows of the same cell are fine
+    }
+
+    #[test]
+    #[should_panic]
+    fn ord_while_mut_borrowed() {
+        let a = RefCell::new(1);
+        let b = RefCell::new(2);
+        let _guard = a.borrow_mut();
+        let _ = a < b;
+    }
 }
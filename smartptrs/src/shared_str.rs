@@ -0,0 +1,117 @@
+use std::ops::Deref;
+
+use crate::rc::Rc;
+
+/// A string for the "cloned a lot, rarely mutated" case. While shared, clones only bump the
+/// `Rc<str>` count; the first mutation copies it into a private `String`, and `share` turns
+/// it back into a cheaply clonable one.
+#[derive(Clone)]
+pub enum SharedStr {
+    Shared(Rc<str>),
+    Owned(String),
+}
+
+impl SharedStr {
+    pub fn new(s: &str) -> SharedStr {
+        SharedStr::Shared(Rc::from(s))
+    }
+
+    /// Mutable access, copying the string out of the shared allocation first if needed.
+    pub fn to_mut(&mut self) -> &mut String {
+        if let SharedStr::Shared(rc) = self {
+            // Rc<str> can't grow in place, so even a unique one has to be copied
+            *self = SharedStr::Owned(String::from(&**rc));
+        }
+        match self {
+            SharedStr::Owned(s) => s,
+            SharedStr::Shared(_) => unreachable!(),
+        }
+    }
+
+    /// Moves an owned string into a shared allocation so later clones are cheap.
+    pub fn share(&mut self) {
+        if let SharedStr::Owned(s) = self {
+            *self = SharedStr::Shared(Rc::from(s.as_str()));
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        matches!(self, SharedStr::Shared(_))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            SharedStr::Shared(rc) => rc,
+            SharedStr::Owned(s) => s,
+        }
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for SharedStr {
+    fn from(s: &str) -> Self {
+        SharedStr::new(s)
+    }
+}
+
+impl From<String> for SharedStr {
+    fn from(s: String) -> Self {
+        SharedStr::Owned(s)
+    }
+}
+
+impl PartialEq for SharedStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SharedStr {}
+
+impl std::fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clone_shares() {
+        let a = SharedStr::new("hello");
+        let b = a.clone();
+        let (SharedStr::Shared(ra), SharedStr::Shared(rb)) = (&a, &b) else {
+            panic!("clone of a shared string should stay shared");
+        };
+        assert!(Rc::ptr_eq(ra, rb));
+        assert_eq!(Rc::strong_count(ra), 2);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn copy_on_write() {
+        let a = SharedStr::new("hello");
+        let mut b = a.clone();
+        b.to_mut().push_str(" world");
+
+        assert!(!b.is_shared());
+        assert_eq!(&*a, "hello");
+        assert_eq!(&*b, "hello world");
+        if let SharedStr::Shared(ra) = &a {
+            assert_eq!(Rc::strong_count(ra), 1);
+        }
+
+        b.share();
+        let c = b.clone();
+        assert!(c.is_shared());
+        assert_eq!(c, SharedStr::from(String::from("hello world")));
+    }
+}
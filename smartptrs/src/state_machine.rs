@@ -0,0 +1,81 @@
+use crate::refcell::{Ref, RefCell};
+
+type Transition<S, E> = Box<dyn Fn(&S, E) -> S>;
+
+/// Runs events through a transition function, keeping the current state in a `RefCell`.
+pub struct StateMachine<S, E> {
+    state: RefCell<S>,
+    transition: Transition<S, E>,
+}
+
+impl<S, E> StateMachine<S, E> {
+    pub fn new(initial: S, transition: impl Fn(&S, E) -> S + 'static) -> StateMachine<S, E> {
+        Self {
+            state: RefCell::new(initial),
+            transition: Box::new(transition),
+        }
+    }
+
+    pub fn dispatch(&self, event: E) {
+        // the transition only gets a shared borrow, so it may call `state()`; the exclusive
+        // borrow is taken afterwards, just long enough to store the result
+        let next = {
+            let current = self.state.borrow();
+            (self.transition)(&current, event)
+        };
+        *self.state.borrow_mut() = next;
+    }
+
+    /// The current state. Don't hold on to it across `dispatch`, which would panic.
+    pub fn state(&self) -> Ref<'_, S> {
+        self.state.borrow()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Light {
+        Off,
+        On,
+        Broken,
+    }
+
+    enum Event {
+        Toggle,
+        Smash,
+    }
+
+    fn light() -> StateMachine<Light, Event> {
+        StateMachine::new(Light::Off, |state, event| match (*state, event) {
+            (Light::Broken, _) => Light::Broken,
+            (_, Event::Smash) => Light::Broken,
+            (Light::Off, Event::Toggle) => Light::On,
+            (Light::On, Event::Toggle) => Light::Off,
+        })
+    }
+
+    #[test]
+    fn toggle() {
+        let m = light();
+        assert_eq!(*m.state(), Light::Off);
+        m.dispatch(Event::Toggle);
+        assert_eq!(*m.state(), Light::On);
+        m.dispatch(Event::Toggle);
+        m.dispatch(Event::Toggle);
+        assert_eq!(*m.state(), Light::On);
+        m.dispatch(Event::Smash);
+        m.dispatch(Event::Toggle);
+        assert_eq!(*m.state(), Light::Broken);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dispatch_while_inspecting() {
+        let m = light();
+        let _state = m.state();
+        m.dispatch(Event::Toggle);
+    }
+}
@@ -0,0 +1,7 @@
+use smartptrs::refcell::RefCell;
+
+fn assert_sync<T: Sync>() {}
+
+fn main() {
+    assert_sync::<RefCell<i32>>();
+}